@@ -1,30 +1,114 @@
 use dialoguer::{Confirm, Input};
+use std::fs;
+use std::path::PathBuf;
 
-use crate::commands::{ensure_vault_exists, get_master_password, get_storage};
+use crate::commands::{
+    apply_field_args, ensure_vault_exists, get_master_password, get_storage, open_hibp_file,
+    styled_strength, unlock_password,
+};
 use crate::models::credential::Credential;
-use crate::utils::generator::genrate_password;
+use crate::models::folder;
+use crate::models::item::{
+    ApiTokenDetails, CardDetails, IdentityDetails, ItemDetails, ItemKind, SshKeyDetails,
+};
+use crate::utils::generator::{
+    DEFAULT_LENGTH, PassphraseOptions, generate_passphrase, generate_with_policy, genrate_password,
+};
+use crate::utils::hibp::HashFile;
+use crate::utils::password_rules;
+use crate::utils::strength;
 // use crate::utils::crypto::{decrypt_password, encrypt_password};
 
-pub fn run(
-    title: String,
-    username: Option<String>,
-    url: Option<String>,
-    notes: Option<String>,
-    generate: bool,
-) -> anyhow::Result<()> {
+/// Values given on the command line
+pub struct AddArgs {
+    pub username: Option<String>,
+    pub url: Option<String>,
+    pub notes: Option<String>,
+    pub generate: bool,
+    pub kind: ItemKind,
+    pub folder: Option<String>,
+    pub tags: Vec<String>,
+    pub fields: Vec<String>,
+    pub secret_fields: Vec<String>,
+    pub hibp_file: Option<PathBuf>,
+    /// Site password rules that `--generate` follows
+    pub password_rules: Option<String>,
+    /// Generate a Diceware passphrase with these settings
+    pub passphrase: Option<PassphraseOptions>,
+}
+
+impl AddArgs {
+    /// The first flag given that only makes sense for a login
+    fn login_only_flag(&self) -> Option<&'static str> {
+        [
+            ("--username", self.username.is_some()),
+            ("--url", self.url.is_some()),
+            ("--generate", self.generate),
+            ("--password-rules", self.password_rules.is_some()),
+            ("--hibp-file", self.hibp_file.is_some()),
+            ("--passphrase", self.passphrase.is_some()),
+        ]
+        .into_iter()
+        .find_map(|(flag, given)| given.then_some(flag))
+    }
+}
+
+pub fn run(title: String, args: AddArgs) -> anyhow::Result<()> {
+    // Other item types have no place for these, so don't drop them silently
+    if args.kind != ItemKind::Login
+        && let Some(flag) = args.login_only_flag()
+    {
+        return Err(anyhow::anyhow!(
+            "❌ {} only applies to logins, not to {} items",
+            flag,
+            args.kind
+        ));
+    }
+
+    let AddArgs {
+        username,
+        url,
+        notes,
+        generate,
+        kind,
+        folder,
+        tags,
+        fields,
+        secret_fields,
+        hibp_file,
+        password_rules,
+        passphrase,
+    } = args;
+
+    let folder = match folder {
+        Some(folder) => folder::normalize(&folder)?,
+        None => None,
+    };
+
+    if kind != ItemKind::Login {
+        return add_item(title, kind, folder, tags, notes, fields, secret_fields);
+    }
+
     println!("➕ Adding new credential: {}", title);
 
+    // Catch a typo in the rules before any prompts
+    let policy = match &password_rules {
+        Some(rules) => Some(password_rules::parse(rules)?),
+        None => None,
+    };
+
     let storage = get_storage()?;
     ensure_vault_exists(&storage)?;
+    let mut breaches = open_hibp_file(&storage, hibp_file)?;
 
     // Get master password
-    let master_password = get_master_password("🔐 Enter master password to unlock vault")?;
+    let master_password = unlock_password()?;
 
     // Load existing vault
     let mut vault = storage.load_vault(&master_password)?;
 
     // Collect credentails information
-    let _username: Option<String> = match username {
+    let username: Option<String> = match username {
         Some(u) => Some(u),
         None => {
             let input: String = Input::new()
@@ -58,7 +142,22 @@ pub fn run(
     };
 
     // Handle password
-    let password = if generate {
+    let password = if let Some(options) = passphrase {
+        let generated = generate_passphrase(&options)?;
+        println!(
+            "🎲 Generated passphrase: {} ({:.0} bits)",
+            generated.phrase, generated.entropy
+        );
+        generated.phrase
+    } else if let (true, Some(policy)) = (generate, &policy) {
+        // The site rules already say what length and characters work
+        let generated = generate_with_policy(DEFAULT_LENGTH, policy)?;
+        println!(
+            "🎲 Generated password following the site rules: {}",
+            generated
+        );
+        generated
+    } else if generate {
         let length: usize = Input::new()
             .with_prompt("password length")
             .default(16)
@@ -73,11 +172,24 @@ pub fn run(
         println!(" Genrated password: {}", generated);
         generated
     } else {
-        get_master_password("Enter password for the credential:")?
+        let user_inputs = [title.as_str(), username.as_deref().unwrap_or_default()];
+        loop {
+            let password = get_master_password("Enter password for the credential:")?;
+            if accept_password(&password, &user_inputs, breaches.as_mut())? {
+                break password;
+            }
+        }
     };
 
     // Create credental
-    let mut credential = Credential::new(title, password);
+    let mut credential = Credential::new(title, password)
+        .with_folder(folder)
+        .with_tags(tags);
+    credential.password_rules = password_rules;
+
+    if let Some(u) = username {
+        credential = credential.with_username(u);
+    }
 
     if let Some(u) = url {
         credential = credential.with_url(u);
@@ -87,12 +199,249 @@ pub fn run(
         credential = credential.with_notes(n)
     }
 
+    apply_field_args(&mut credential, &fields, &secret_fields)?;
+    let strength = credential.password_strength();
+
     // Add to vault and save
     vault.add_credentail(credential);
     storage.save_vault(&vault, &master_password)?;
 
     println!("✅ Credential added successfully!");
+    println!("💪 Password strength: {}", styled_strength(&strength));
+    println!("📊 Total credentials in vault: {}", vault.credentials.len());
+
+    Ok(())
+}
+
+/// Warn about a weak or breached password; true when it is fine or kept anyway
+fn accept_password(
+    password: &str,
+    user_inputs: &[&str],
+    breaches: Option<&mut HashFile>,
+) -> anyhow::Result<bool> {
+    let strength = strength::estimate(password, user_inputs);
+    let breach_count = match breaches {
+        Some(hashes) => hashes.count(password)?,
+        None => 0,
+    };
+    if !strength.is_weak() && breach_count == 0 {
+        return Ok(true);
+    }
+
+    if strength.is_weak() {
+        println!("⚠️ This password is {}", styled_strength(&strength));
+        if let Some(warning) = strength.warning() {
+            println!("💡 It {}", warning);
+        }
+    }
+    if breach_count > 0 {
+        println!(
+            "🚨 This password appears {} times in known data breaches",
+            breach_count
+        );
+    }
+
+    Ok(Confirm::new()
+        .with_prompt("Use it anyway?")
+        .default(false)
+        .interact()?)
+}
+
+/// Add a typed item (card, identity, SSH key, ...) with its own prompts
+fn add_item(
+    title: String,
+    kind: ItemKind,
+    folder: Option<String>,
+    tags: Vec<String>,
+    notes: Option<String>,
+    fields: Vec<String>,
+    secret_fields: Vec<String>,
+) -> anyhow::Result<()> {
+    println!("➕ Adding new {}: {}", kind, title);
+
+    let storage = get_storage()?;
+    ensure_vault_exists(&storage)?;
+
+    let master_password = unlock_password()?;
+    let mut vault = storage.load_vault(&master_password)?;
+
+    let details = prompt_details(kind)?;
+
+    // A secure note is all notes, so it is not optional there
+    let notes = match notes {
+        Some(n) => Some(n),
+        None => {
+            let prompt = if kind == ItemKind::SecureNote {
+                "Note"
+            } else {
+                "Notes (optional, press Enter to skip)"
+            };
+            let input: String = Input::new()
+                .with_prompt(prompt)
+                .allow_empty(kind != ItemKind::SecureNote)
+                .interact_text()?;
+            optional(input)
+        }
+    };
+
+    let mut credential = Credential::with_details(title, details)
+        .with_folder(folder)
+        .with_tags(tags);
+    if let Some(n) = notes {
+        credential = credential.with_notes(n);
+    }
+    apply_field_args(&mut credential, &fields, &secret_fields)?;
+
+    vault.add_credentail(credential);
+    storage.save_vault(&vault, &master_password)?;
+
+    println!("✅ {} added successfully!", kind);
     println!("📊 Total credentials in vault: {}", vault.credentials.len());
 
     Ok(())
 }
+
+/// Ask for the type-specific fields, re-prompting until they validate
+fn prompt_details(kind: ItemKind) -> anyhow::Result<ItemDetails> {
+    loop {
+        let details = match kind {
+            ItemKind::Login => Ok(ItemDetails::Login),
+            ItemKind::SecureNote => Ok(ItemDetails::SecureNote),
+            ItemKind::Card => prompt_card().map(ItemDetails::Card),
+            ItemKind::Identity => prompt_identity().map(ItemDetails::Identity),
+            ItemKind::SshKey => prompt_ssh_key().map(ItemDetails::SshKey),
+            ItemKind::ApiToken => prompt_api_token().map(ItemDetails::ApiToken),
+        };
+
+        match details {
+            Ok(details) => return Ok(details),
+            Err(e) => {
+                println!("{}", e);
+                let retry = Confirm::new()
+                    .with_prompt("Try again?")
+                    .default(true)
+                    .interact()?;
+                if !retry {
+                    return Err(anyhow::anyhow!("Cancelled"));
+                }
+            }
+        }
+    }
+}
+
+fn prompt_card() -> anyhow::Result<CardDetails> {
+    let cardholder: String = Input::new()
+        .with_prompt("Cardholder name (optional, press Enter to skip)")
+        .allow_empty(true)
+        .interact_text()?;
+    let number = get_master_password("Card number:")?;
+    let expiry: String = Input::new().with_prompt("Expiry (MM/YY)").interact_text()?;
+    let cvv = get_master_password("CVV:")?;
+
+    CardDetails::new(optional(cardholder), &number, &expiry, &cvv)
+}
+
+fn prompt_identity() -> anyhow::Result<IdentityDetails> {
+    let full_name: String = Input::new().with_prompt("Full name").interact_text()?;
+    let ask = |prompt: &str| -> anyhow::Result<Option<String>> {
+        let input: String = Input::new()
+            .with_prompt(format!("{} (optional, press Enter to skip)", prompt))
+            .allow_empty(true)
+            .interact_text()?;
+        Ok(optional(input))
+    };
+
+    let identity = IdentityDetails {
+        full_name,
+        email: ask("Email")?,
+        phone: ask("Phone")?,
+        address_line1: ask("Address line 1")?,
+        address_line2: ask("Address line 2")?,
+        city: ask("City")?,
+        region: ask("State/Region")?,
+        postal_code: ask("Postal code")?,
+        country: ask("Country")?,
+    };
+    identity.validate()?;
+    Ok(identity)
+}
+
+fn prompt_ssh_key() -> anyhow::Result<SshKeyDetails> {
+    // Keys are multi-line, so read them from disk rather than the prompt
+    let path: String = Input::new()
+        .with_prompt("Path to private key file")
+        .default("~/.ssh/id_ed25519".to_string())
+        .interact_text()?;
+    let private_path = expand_home(&path);
+    let private_key = fs::read_to_string(&private_path)
+        .map_err(|e| anyhow::anyhow!("❌ Could not read {}: {}", private_path.display(), e))?;
+
+    let mut public_path = private_path.into_os_string();
+    public_path.push(".pub");
+    let public_path = PathBuf::from(public_path);
+    let public_key = if public_path.exists() {
+        println!("🔑 Using public key from {}", public_path.display());
+        Some(fs::read_to_string(&public_path)?)
+    } else {
+        let input: String = Input::new()
+            .with_prompt("Public key (optional, press Enter to skip)")
+            .allow_empty(true)
+            .interact_text()?;
+        optional(input)
+    };
+
+    SshKeyDetails::new(private_key, public_key)
+}
+
+fn prompt_api_token() -> anyhow::Result<ApiTokenDetails> {
+    let token = get_master_password("API token:")?;
+    let scopes: String = Input::new()
+        .with_prompt("Scopes, comma separated (optional, press Enter to skip)")
+        .allow_empty(true)
+        .interact_text()?;
+
+    ApiTokenDetails::new(token, scopes.split(',').map(str::to_string).collect())
+}
+
+fn expand_home(path: &str) -> PathBuf {
+    match path.strip_prefix("~/") {
+        Some(rest) => dirs::home_dir().unwrap_or_default().join(rest),
+        None => PathBuf::from(path),
+    }
+}
+
+fn optional(input: String) -> Option<String> {
+    if input.is_empty() { None } else { Some(input) }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_login_only_flags() {
+        let args = AddArgs {
+            username: None,
+            url: None,
+            notes: Some("kept for every type".to_string()),
+            generate: false,
+            kind: ItemKind::Card,
+            folder: None,
+            tags: vec!["finance".to_string()],
+            fields: Vec::new(),
+            secret_fields: Vec::new(),
+            hibp_file: None,
+            password_rules: None,
+            passphrase: None,
+        };
+        assert_eq!(args.login_only_flag(), None);
+
+        let args = AddArgs {
+            url: Some("https://bank.example".to_string()),
+            passphrase: Some(PassphraseOptions::default()),
+            ..args
+        };
+        assert_eq!(args.login_only_flag(), Some("--url"));
+        assert!(run("Visa".to_string(), args).is_err());
+    }
+}
@@ -0,0 +1,216 @@
+use crate::commands::resolve::resolve_credential;
+use crate::commands::{ensure_vault_exists, get_master_password, get_storage};
+use crate::models::credential::CredentialVault;
+use crate::storage::attachments::MAX_ATTACHMENT_SIZE;
+use crate::storage::file::FileStorage;
+use crate::utils::Utc;
+use anyhow::Result;
+use dialoguer::Confirm;
+use std::fs;
+use std::path::PathBuf;
+use uuid::Uuid;
+
+/// Encrypt a file and attach it to a credential
+pub fn add(query: String, file: PathBuf, name: Option<String>) -> Result<()> {
+    let (storage, master_password, mut vault, id) = unlock(&query)?;
+
+    let name = match name {
+        Some(name) => name,
+        None => file
+            .file_name()
+            .map(|n| n.to_string_lossy().into_owned())
+            .ok_or_else(|| anyhow::anyhow!("❌ '{}' has no file name", file.display()))?,
+    };
+
+    let credential = vault
+        .find_credential_mut(&id)
+        .ok_or_else(|| anyhow::anyhow!("Credential disappeared while attaching"))?;
+    if credential.attachment(&name).is_some() {
+        return Err(anyhow::anyhow!(
+            "❌ '{}' already has an attachment named '{}' (use --name to pick another)",
+            credential.service,
+            name
+        ));
+    }
+
+    // Check the size first so a huge file by mistake is never read in
+    let size = fs::metadata(&file)
+        .map_err(|e| anyhow::anyhow!("❌ Could not read {}: {}", file.display(), e))?
+        .len();
+    if size > MAX_ATTACHMENT_SIZE {
+        return Err(anyhow::anyhow!(
+            "❌ {} is {}, the attachment limit is {}",
+            file.display(),
+            format_size(size),
+            format_size(MAX_ATTACHMENT_SIZE)
+        ));
+    }
+
+    let data = fs::read(&file)
+        .map_err(|e| anyhow::anyhow!("❌ Could not read {}: {}", file.display(), e))?;
+    let store = storage.attachments();
+    let attachment = store.store(name, &data)?;
+    println!(
+        "📎 Attaching {} ({}) to {}",
+        attachment.name,
+        format_size(attachment.size),
+        credential.service
+    );
+
+    credential.attachments.push(attachment.clone());
+    credential.updated_at = Utc::now();
+    vault.updated_at = Utc::now();
+
+    // Don't leave an orphaned blob behind if the vault can't be saved
+    if let Err(e) = storage.save_vault(&vault, &master_password) {
+        store.remove(&attachment)?;
+        return Err(e);
+    }
+    println!("✅ Attachment added successfully!");
+
+    Ok(())
+}
+
+/// Show the attachments of a credential
+pub fn list(query: String) -> Result<()> {
+    let (_storage, _master_password, vault, id) = unlock(&query)?;
+    let credential = vault
+        .find_credential(&id)
+        .ok_or_else(|| anyhow::anyhow!("Credential not found"))?;
+
+    if credential.attachments.is_empty() {
+        println!("📭 {} has no attachments.", credential.service);
+        return Ok(());
+    }
+
+    println!(
+        "📎 Attachments of {} ({}):\n",
+        credential.service,
+        credential.attachments.len()
+    );
+    for attachment in &credential.attachments {
+        println!(
+            "  {}  {}  {}",
+            attachment.name,
+            format_size(attachment.size),
+            attachment.created_at.format("%Y-%m-%d %H:%M UTC")
+        );
+    }
+
+    Ok(())
+}
+
+/// Decrypt an attachment to a file
+pub fn extract(query: String, name: String, output: Option<PathBuf>, force: bool) -> Result<()> {
+    let (storage, _master_password, vault, id) = unlock(&query)?;
+    let credential = vault
+        .find_credential(&id)
+        .ok_or_else(|| anyhow::anyhow!("Credential not found"))?;
+    let Some(attachment) = credential.attachment(&name) else {
+        println!(
+            "❌ {} has no attachment named '{}'.",
+            credential.service, name
+        );
+        println!("💡 Use 'pwdmgr attach list {}' to see them", query);
+        return Ok(());
+    };
+
+    let output = output.unwrap_or_else(|| PathBuf::from(&attachment.name));
+    if output.exists() && !force {
+        return Err(anyhow::anyhow!(
+            "❌ {} already exists (use --force to overwrite)",
+            output.display()
+        ));
+    }
+
+    let data = storage.attachments().load(attachment)?;
+    fs::write(&output, &data)?;
+
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::PermissionsExt;
+        let mut perms = fs::metadata(&output)?.permissions();
+        perms.set_mode(0o600);
+        fs::set_permissions(&output, perms)?;
+    }
+
+    println!(
+        "✅ Extracted {} ({}) to {}",
+        attachment.name,
+        format_size(attachment.size),
+        output.display()
+    );
+
+    Ok(())
+}
+
+/// Remove an attachment and delete its blob
+pub fn remove(query: String, name: String, force: bool) -> Result<()> {
+    let (storage, master_password, mut vault, id) = unlock(&query)?;
+    let credential = vault
+        .find_credential_mut(&id)
+        .ok_or_else(|| anyhow::anyhow!("Credential not found"))?;
+    let Some(index) = credential
+        .attachments
+        .iter()
+        .position(|a| a.name.eq_ignore_ascii_case(&name))
+    else {
+        println!(
+            "❌ {} has no attachment named '{}'.",
+            credential.service, name
+        );
+        return Ok(());
+    };
+
+    if !force {
+        let confirm = Confirm::new()
+            .with_prompt(format!(
+                "Delete attachment '{}' from {}?",
+                credential.attachments[index].name, credential.service
+            ))
+            .default(false)
+            .interact()?;
+        if !confirm {
+            println!("❌ Removal cancelled.");
+            return Ok(());
+        }
+    }
+
+    let attachment = credential.attachments.remove(index);
+    credential.updated_at = Utc::now();
+    vault.updated_at = Utc::now();
+
+    // Save first so the vault never points at a deleted blob, then commit both together
+    storage.write_vault(&vault, &master_password)?;
+    storage.attachments().remove(&attachment)?;
+    storage.commit_if_tracked("Remove attachment");
+    println!("✅ Attachment '{}' removed.", attachment.name);
+
+    Ok(())
+}
+
+/// Open the vault and find the credential the attachment command is about
+fn unlock(query: &str) -> Result<(FileStorage, String, CredentialVault, Uuid)> {
+    let storage = get_storage()?;
+    ensure_vault_exists(&storage)?;
+
+    let master_password = get_master_password("🔐 Enter master password to unlock vault:")?;
+    let vault = storage.load_vault(&master_password)?;
+
+    let Some(id) = resolve_credential(&vault, query)? else {
+        return Err(anyhow::anyhow!(
+            "❌ No credential found matching '{}'.",
+            query
+        ));
+    };
+
+    Ok((storage, master_password, vault, id))
+}
+
+fn format_size(bytes: u64) -> String {
+    match bytes {
+        b if b >= 1024 * 1024 => format!("{:.1} MiB", b as f64 / (1024.0 * 1024.0)),
+        b if b >= 1024 => format!("{:.1} KiB", b as f64 / 1024.0),
+        b => format!("{} B", b),
+    }
+}
@@ -0,0 +1,313 @@
+use crate::commands::{
+    ensure_vault_exists, get_storage, styled_strength, unlock_password, unlock_vault_quietly,
+};
+use crate::models::credential::{Credential, CredentialVault};
+use crate::models::item::ItemKind;
+use crate::utils::strength::Strength;
+use crate::utils::{DateTime, Utc};
+use anyhow::Result;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::str::FromStr;
+use uuid::Uuid;
+
+/// Default `--max-age` in days
+pub const DEFAULT_MAX_AGE: i64 = 365;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Check {
+    Reused,
+    Weak,
+    Old,
+    InsecureUrl,
+    Missing2fa,
+}
+
+pub const ALL_CHECKS: [Check; 5] = [
+    Check::Reused,
+    Check::Weak,
+    Check::Old,
+    Check::InsecureUrl,
+    Check::Missing2fa,
+];
+
+impl FromStr for Check {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "reused" => Ok(Check::Reused),
+            "weak" => Ok(Check::Weak),
+            "old" => Ok(Check::Old),
+            "insecure-url" | "http" => Ok(Check::InsecureUrl),
+            "missing-2fa" | "2fa" => Ok(Check::Missing2fa),
+            _ => Err(anyhow::anyhow!(
+                "❌ Unknown check '{}'. Use reused, weak, old, insecure-url or missing-2fa",
+                s
+            )),
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct Entry {
+    pub id: Uuid,
+    pub service: String,
+    pub username: Option<String>,
+}
+
+impl Entry {
+    fn new(credential: &Credential) -> Self {
+        Entry {
+            id: credential.id,
+            service: credential.service.clone(),
+            username: credential.username.clone(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize)]
+pub struct WeakEntry {
+    #[serde(flatten)]
+    pub entry: Entry,
+    pub strength: Strength,
+}
+
+#[derive(Debug, Serialize)]
+pub struct OldEntry {
+    #[serde(flatten)]
+    pub entry: Entry,
+    pub updated_at: DateTime<Utc>,
+    pub days: i64,
+}
+
+#[derive(Debug, Serialize)]
+pub struct UrlEntry {
+    #[serde(flatten)]
+    pub entry: Entry,
+    pub url: String,
+}
+
+/// Findings of `pwdmgr audit passwords`; password values are never included
+#[derive(Debug, Default, Serialize)]
+pub struct Report {
+    pub credentials: usize,
+    pub issues: usize,
+    pub reused: Vec<Vec<Entry>>,
+    pub weak: Vec<WeakEntry>,
+    pub old: Vec<OldEntry>,
+    pub insecure_url: Vec<UrlEntry>,
+    pub missing_2fa: Vec<Entry>,
+}
+
+/// Check every login in the vault and fail (for CI) when anything is found
+pub fn passwords(checks: Vec<Check>, max_age: i64, json: bool) -> Result<()> {
+    let storage = get_storage()?;
+    ensure_vault_exists(&storage)?;
+
+    // JSON goes to stdout untouched, so the unlock messages must not
+    let vault = if json {
+        unlock_vault_quietly(&storage)?
+    } else {
+        let master_password = unlock_password()?;
+        storage.load_vault(&master_password)?
+    };
+
+    let checks = if checks.is_empty() {
+        ALL_CHECKS.to_vec()
+    } else {
+        checks
+    };
+    let report = audit(&vault, &checks, max_age, Utc::now());
+
+    if json {
+        println!("{}", serde_json::to_string_pretty(&report)?);
+    } else {
+        print_report(&report, max_age);
+    }
+
+    match report.issues {
+        0 => Ok(()),
+        n => Err(anyhow::anyhow!("❌ Audit found {} issue(s)", n)),
+    }
+}
+
+pub fn audit(
+    vault: &CredentialVault,
+    checks: &[Check],
+    max_age: i64,
+    now: DateTime<Utc>,
+) -> Report {
+    let logins: Vec<&Credential> = vault
+        .credentials
+        .iter()
+        .filter(|c| c.kind() == ItemKind::Login)
+        .collect();
+    let mut report = Report {
+        credentials: logins.len(),
+        ..Default::default()
+    };
+
+    if checks.contains(&Check::Reused) {
+        let mut by_password: HashMap<&str, Vec<&Credential>> = HashMap::new();
+        for credential in logins.iter().filter(|c| !c.password.is_empty()) {
+            by_password
+                .entry(&credential.password)
+                .or_default()
+                .push(credential);
+        }
+        report.reused = by_password
+            .into_values()
+            .filter(|group| group.len() > 1)
+            .map(|group| group.into_iter().map(Entry::new).collect())
+            .collect();
+        report
+            .reused
+            .sort_by(|a, b| a[0].service.cmp(&b[0].service));
+    }
+
+    for credential in &logins {
+        if checks.contains(&Check::Weak) && !credential.password.is_empty() {
+            let strength = credential.password_strength();
+            if strength.is_weak() {
+                report.weak.push(WeakEntry {
+                    entry: Entry::new(credential),
+                    strength,
+                });
+            }
+        }
+
+        let days = (now - credential.updated_at).num_days();
+        if checks.contains(&Check::Old) && days >= max_age {
+            report.old.push(OldEntry {
+                entry: Entry::new(credential),
+                updated_at: credential.updated_at,
+                days,
+            });
+        }
+
+        if let Some(url) = &credential.url
+            && checks.contains(&Check::InsecureUrl)
+            && url.trim().to_lowercase().starts_with("http://")
+        {
+            report.insecure_url.push(UrlEntry {
+                entry: Entry::new(credential),
+                url: url.clone(),
+            });
+        }
+
+        if checks.contains(&Check::Missing2fa) && credential.otp_field().is_none() {
+            report.missing_2fa.push(Entry::new(credential));
+        }
+    }
+
+    report.issues = report.reused.iter().map(Vec::len).sum::<usize>()
+        + report.weak.len()
+        + report.old.len()
+        + report.insecure_url.len()
+        + report.missing_2fa.len();
+    report
+}
+
+fn print_report(report: &Report, max_age: i64) {
+    let name = |entry: &Entry| match &entry.username {
+        Some(username) => format!("{} ({})", entry.service, username),
+        None => entry.service.clone(),
+    };
+
+    println!("🛡️ Password audit of {} logins", report.credentials);
+
+    if !report.reused.is_empty() {
+        println!("\n🔁 Reused passwords ({} groups):", report.reused.len());
+        for group in &report.reused {
+            let names: Vec<String> = group.iter().map(name).collect();
+            println!("  - {}", names.join(", "));
+        }
+    }
+
+    if !report.weak.is_empty() {
+        println!("\n🪫 Weak passwords ({}):", report.weak.len());
+        for weak in &report.weak {
+            let strength = styled_strength(&weak.strength);
+            match weak.strength.warning() {
+                Some(warning) => println!("  - {}: {}, {}", name(&weak.entry), strength, warning),
+                None => println!("  - {}: {}", name(&weak.entry), strength),
+            }
+        }
+    }
+
+    if !report.old.is_empty() {
+        println!(
+            "\n⏳ Not changed in {}+ days ({}):",
+            max_age,
+            report.old.len()
+        );
+        for old in &report.old {
+            println!("  - {}: {} days", name(&old.entry), old.days);
+        }
+    }
+
+    if !report.insecure_url.is_empty() {
+        println!(
+            "\n🔓 Insecure http:// URLs ({}):",
+            report.insecure_url.len()
+        );
+        for entry in &report.insecure_url {
+            println!("  - {}: {}", name(&entry.entry), entry.url);
+        }
+    }
+
+    if !report.missing_2fa.is_empty() {
+        println!(
+            "\n🔐 No two-factor code stored ({}):",
+            report.missing_2fa.len()
+        );
+        for entry in &report.missing_2fa {
+            println!("  - {}", name(entry));
+        }
+    }
+
+    println!();
+    if report.issues == 0 {
+        println!("✅ No issues found.");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    #[test]
+    fn test_audit() {
+        let now = Utc::now();
+        let mut vault = CredentialVault::new();
+
+        let mut old = Credential::new("Forum".to_string(), "password".to_string())
+            .with_url("http://forum.example.com".to_string());
+        old.updated_at = now - Duration::days(400);
+        vault.add_credentail(old);
+        vault.add_credentail(Credential::new("Shop".to_string(), "password".to_string()));
+        let mut secure = Credential::new("Mail".to_string(), "v8#Lq2!pZr9mXw".to_string())
+            .with_url("https://mail.example.com".to_string());
+        secure.set_field("otp".to_string(), "JBSWY3DPEHPK3PXP".to_string(), true);
+        vault.add_credentail(secure);
+
+        let report = audit(&vault, &ALL_CHECKS, DEFAULT_MAX_AGE, now);
+        assert_eq!(report.credentials, 3);
+        assert_eq!(report.reused.len(), 1);
+        assert_eq!(report.reused[0].len(), 2);
+        assert_eq!(report.weak.len(), 2);
+        assert_eq!(report.old.len(), 1);
+        assert_eq!(report.old[0].days, 400);
+        assert_eq!(report.insecure_url.len(), 1);
+        assert_eq!(report.missing_2fa.len(), 2);
+        assert_eq!(report.issues, 8);
+
+        let json = serde_json::to_string(&report).unwrap();
+        assert!(!json.contains("v8#Lq2") && !json.contains("\"password\""));
+
+        let report = audit(&vault, &[Check::InsecureUrl], DEFAULT_MAX_AGE, now);
+        assert_eq!(report.issues, 1);
+    }
+}
@@ -0,0 +1,55 @@
+use crate::commands::{ensure_vault_exists, get_storage, open_hibp_file, unlock_password};
+use anyhow::Result;
+use std::path::PathBuf;
+
+/// Look up every password in an offline HIBP dump; nothing leaves the machine
+pub fn run(hibp_file: Option<PathBuf>) -> Result<()> {
+    let storage = get_storage()?;
+    ensure_vault_exists(&storage)?;
+
+    let Some(mut hashes) = open_hibp_file(&storage, hibp_file)? else {
+        return Err(anyhow::anyhow!(
+            "❌ No breach file given. Use --hibp-file <path> or set \"hibp_file\" in config.json"
+        ));
+    };
+
+    let master_password = unlock_password()?;
+    let vault = storage.load_vault(&master_password)?;
+
+    let mut checked = 0;
+    let mut breached = Vec::new();
+    for credential in vault.credentials.iter().filter(|c| !c.password.is_empty()) {
+        checked += 1;
+        let count = hashes.count(&credential.password)?;
+        if count > 0 {
+            breached.push((credential, count));
+        }
+    }
+
+    if breached.is_empty() {
+        println!(
+            "✅ None of the {} passwords appear in known breaches.",
+            checked
+        );
+        return Ok(());
+    }
+
+    // Most exposed first, those are the ones to change today
+    breached.sort_by_key(|(_, count)| std::cmp::Reverse(*count));
+    println!("🚨 Passwords found in known breaches:");
+    for (credential, count) in &breached {
+        match &credential.username {
+            Some(username) => println!(
+                "  - {} ({}): seen {} times",
+                credential.service, username, count
+            ),
+            None => println!("  - {}: seen {} times", credential.service, count),
+        }
+    }
+
+    Err(anyhow::anyhow!(
+        "❌ {} of {} passwords are breached",
+        breached.len(),
+        checked
+    ))
+}
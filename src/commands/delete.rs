@@ -1,39 +1,22 @@
 use anyhow::Result;
 use dialoguer::Confirm;
-use uuid::Uuid;
 
-use crate::commands::{ensure_vault_exists, get_master_password, get_storage};
+use crate::commands::resolve::resolve_credential;
+use crate::commands::{ensure_vault_exists, get_storage, unlock_password};
 
 pub fn run(query: String, force: bool) -> Result<()> {
     let storage = get_storage()?;
     ensure_vault_exists(&storage)?;
 
     // Ask for master password securely
-    let master_password = get_master_password("🔐 Enter master password to unlock vault:")?;
+    let master_password = unlock_password()?;
 
     // Load existing vault
     let mut vault = storage.load_vault(&master_password)?;
 
-    // Try parsing query as UUID
-    let credential_option = if let Ok(uuid) = Uuid::parse_str(&query) {
-        vault.find_credential(&uuid).cloned()
-    } else {
-        vault
-            .credentials
-            .iter()
-            .find(|cred| {
-                cred.service.to_lowercase().contains(&query.to_lowercase())
-                    || cred
-                        .username
-                        .as_ref()
-                        .map_or(false, |u| u.to_lowercase().contains(&query.to_lowercase()))
-                    || cred
-                        .url
-                        .as_ref()
-                        .map_or(false, |u| u.to_lowercase().contains(&query.to_lowercase()))
-            })
-            .cloned()
-    };
+    // Ranked lookup; ambiguous queries ask (or fail) instead of taking the first hit
+    let credential_option =
+        resolve_credential(&vault, &query)?.and_then(|id| vault.find_credential(&id).cloned());
 
     if let Some(credential) = credential_option {
         if !force {
@@ -50,8 +33,18 @@ pub fn run(query: String, force: bool) -> Result<()> {
         }
 
         // Remove credential by ID
-        if vault.remove_credential(&credential.id).is_some() {
-            storage.save_vault(&vault, &master_password)?;
+        if let Some(removed) = vault.remove_credential(&credential.id) {
+            if removed.attachments.is_empty() {
+                storage.save_vault(&vault, &master_password)?;
+            } else {
+                // Drop the blobs only once the vault no longer references them
+                storage.write_vault(&vault, &master_password)?;
+                let store = storage.attachments();
+                for attachment in &removed.attachments {
+                    store.remove(attachment)?;
+                }
+                storage.commit_if_tracked("Update vault");
+            }
             println!("✅ credential deleted successfully.");
         } else {
             println!("❌ credential not found during Deletion.");
@@ -0,0 +1,95 @@
+use crate::commands::{
+    ensure_vault_exists, get_master_password, get_storage, load_other_vault, mask_secret,
+};
+use crate::models::merge::{CredentialChange, FieldChange, diff_vaults};
+use anyhow::Result;
+use console::Style;
+use std::path::PathBuf;
+
+pub fn run(other: PathBuf) -> Result<()> {
+    let storage = get_storage()?;
+    ensure_vault_exists(&storage)?;
+
+    let master_password = get_master_password("🔐 Enter master password to unlock vault:")?;
+    let vault = storage.load_vault(&master_password)?;
+    let other_vault = load_other_vault(&other, &master_password)?;
+
+    let changes = diff_vaults(&vault, &other_vault);
+
+    println!("🔍 Comparing local vault with: {}\n", other.display());
+
+    if changes.is_empty() {
+        println!("✅ Vaults contain identical credentials.");
+        return Ok(());
+    }
+
+    let added_style = Style::new().green();
+    let removed_style = Style::new().red();
+    let changed_style = Style::new().yellow();
+    let id_style = Style::new().dim();
+
+    let (mut added, mut removed, mut changed) = (0, 0, 0);
+
+    for change in &changes {
+        match change {
+            CredentialChange::Added(cred) => {
+                added += 1;
+                println!(
+                    "{} {}",
+                    added_style.apply_to(format!("+ {}", cred.service)),
+                    id_style.apply_to(cred.id)
+                );
+            }
+            CredentialChange::Removed(cred) => {
+                removed += 1;
+                println!(
+                    "{} {}",
+                    removed_style.apply_to(format!("- {}", cred.service)),
+                    id_style.apply_to(cred.id)
+                );
+            }
+            CredentialChange::Changed { ours, fields, .. } => {
+                changed += 1;
+                println!(
+                    "{} {}",
+                    changed_style.apply_to(format!("~ {}", ours.service)),
+                    id_style.apply_to(ours.id)
+                );
+                for field in fields {
+                    println!("    {}", describe_field(field));
+                }
+            }
+        }
+    }
+
+    println!(
+        "\n📊 {} only in other vault, {} only in local vault, {} changed",
+        added, removed, changed
+    );
+
+    Ok(())
+}
+
+/// One-line description of a field change, secrets masked
+pub fn describe_field(change: &FieldChange) -> String {
+    let show = |value: &Option<String>| {
+        if change.secret {
+            mask_secret(value.as_deref()).to_string()
+        } else {
+            value.clone().unwrap_or_else(|| "(none)".to_string())
+        }
+    };
+
+    let suffix = if change.secret {
+        " (secret changed)"
+    } else {
+        ""
+    };
+    format!(
+        "{}: {} → {}{}",
+        change.field,
+        show(&change.ours),
+        show(&change.theirs),
+        suffix
+    )
+}
@@ -0,0 +1,145 @@
+use crate::commands::resolve::resolve_credential;
+use crate::commands::{apply_field_args, ensure_vault_exists, get_master_password, get_storage};
+use crate::models::credential::Credential;
+use crate::utils::Utc;
+use crate::utils::generator::{DEFAULT_LENGTH, generate_with_policy};
+use crate::utils::password_rules;
+use anyhow::Result;
+use dialoguer::{Confirm, Input};
+
+/// Changes requested on the command line
+pub struct EditArgs {
+    pub service: Option<String>,
+    pub username: Option<String>,
+    pub url: Option<String>,
+    pub notes: Option<String>,
+    pub password: bool,
+    pub regenerate: bool,
+    pub password_rules: Option<String>,
+    pub fields: Vec<String>,
+    pub secret_fields: Vec<String>,
+    pub remove_fields: Vec<String>,
+}
+
+impl EditArgs {
+    fn is_empty(&self) -> bool {
+        self.service.is_none()
+            && self.username.is_none()
+            && self.url.is_none()
+            && self.notes.is_none()
+            && !self.password
+            && !self.regenerate
+            && self.password_rules.is_none()
+            && self.fields.is_empty()
+            && self.secret_fields.is_empty()
+            && self.remove_fields.is_empty()
+    }
+}
+
+pub fn run(query: String, args: EditArgs) -> Result<()> {
+    let storage = get_storage()?;
+    ensure_vault_exists(&storage)?;
+
+    let master_password = get_master_password("🔐 Enter master password to unlock vault:")?;
+    let mut vault = storage.load_vault(&master_password)?;
+
+    let Some(id) = resolve_credential(&vault, &query)? else {
+        println!("❌ No credential found matching '{}'.", query);
+        return Ok(());
+    };
+
+    let interactive = args.is_empty();
+    let credential = vault
+        .find_credential_mut(&id)
+        .ok_or_else(|| anyhow::anyhow!("Credential disappeared while editing"))?;
+
+    println!("✏️ Editing credential: {}", credential.service);
+
+    if interactive {
+        // No flags given: walk through every field
+        prompt_changes(credential)?;
+    } else {
+        if let Some(service) = args.service {
+            credential.service = service;
+        }
+        if let Some(username) = args.username {
+            credential.username = non_empty(username);
+        }
+        if let Some(url) = args.url {
+            credential.url = non_empty(url);
+        }
+        if let Some(notes) = args.notes {
+            credential.notes = non_empty(notes);
+        }
+        if let Some(rules) = args.password_rules {
+            let rules = non_empty(rules);
+            if let Some(rules) = &rules {
+                password_rules::parse(rules)?;
+            }
+            credential.password_rules = rules;
+        }
+        if args.password {
+            credential.password = get_master_password("Enter new password for the credential:")?;
+        }
+        if args.regenerate {
+            let password = generate_with_policy(DEFAULT_LENGTH, &credential.password_policy()?)?;
+            println!("🎲 Generated password: {}", password);
+            credential.password = password;
+        }
+
+        for name in &args.remove_fields {
+            if !credential.remove_field(name) {
+                println!("⚠️ No custom field named '{}'", name);
+            }
+        }
+        apply_field_args(credential, &args.fields, &args.secret_fields)?;
+    }
+
+    credential.updated_at = Utc::now();
+    vault.updated_at = Utc::now();
+
+    storage.save_vault(&vault, &master_password)?;
+    println!("✅ Credential updated successfully!");
+
+    Ok(())
+}
+
+/// Walk through the editable fields with the current values as defaults
+pub fn prompt_changes(credential: &mut Credential) -> Result<()> {
+    credential.service = Input::new()
+        .with_prompt("Title")
+        .default(credential.service.clone())
+        .interact_text()?;
+    credential.username = prompt_optional("Username", credential.username.take())?;
+    credential.url = prompt_optional("URL", credential.url.take())?;
+    credential.notes = prompt_optional("Notes", credential.notes.take())?;
+
+    let change_password = Confirm::new()
+        .with_prompt("Change password?")
+        .default(false)
+        .interact()?;
+    if change_password {
+        credential.password = get_master_password("Enter new password for the credential:")?;
+    }
+
+    Ok(())
+}
+
+/// Prompt with the current value as default; '-' clears the field
+fn prompt_optional(prompt: &str, current: Option<String>) -> Result<Option<String>> {
+    let input: String = Input::new()
+        .with_prompt(format!("{} (press Enter to keep, '-' to clear)", prompt))
+        .default(current.unwrap_or_default())
+        .allow_empty(true)
+        .interact_text()?;
+
+    if input == "-" {
+        return Ok(None);
+    }
+    Ok(non_empty(input))
+}
+
+/// Treat an empty string as "unset"
+fn non_empty(value: String) -> Option<String> {
+    if value.is_empty() { None } else { Some(value) }
+}
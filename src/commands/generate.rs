@@ -0,0 +1,77 @@
+use crate::commands::{copy_secret, get_storage, styled_strength};
+use crate::utils::clipboard::Backend;
+use crate::utils::generator::{
+    PassphraseOptions, Policy, eff_wordlist, generate_passphrase_from, generate_with_policy,
+    load_wordlist,
+};
+use crate::utils::strength::{self, Strength};
+use anyhow::Result;
+
+/// Values given on the command line
+pub struct GenerateArgs {
+    pub length: usize,
+    pub policy: Policy,
+    /// Generate passphrases with these settings instead
+    pub passphrase: Option<PassphraseOptions>,
+    pub count: usize,
+    pub copy: bool,
+    pub strength: bool,
+}
+
+/// Print passwords one per line, or copy one; no vault is needed
+pub fn run(args: GenerateArgs) -> Result<()> {
+    if args.count == 0 {
+        return Err(anyhow::anyhow!("❌ --count must be at least 1"));
+    }
+    if args.copy && args.count > 1 {
+        return Err(anyhow::anyhow!("❌ --copy works with a single password"));
+    }
+
+    // Read a custom wordlist once, not for every passphrase
+    let custom = match args.passphrase.as_ref().and_then(|o| o.wordlist.as_ref()) {
+        Some(path) => Some(load_wordlist(path)?),
+        None => None,
+    };
+    let wordlist = custom.as_deref().unwrap_or(eff_wordlist());
+
+    for _ in 0..args.count {
+        let (password, rating) = match &args.passphrase {
+            // A passphrase's entropy is known exactly, no need to estimate it
+            Some(options) => {
+                let passphrase = generate_passphrase_from(wordlist, options)?;
+                let rating = Strength::from_entropy(passphrase.entropy);
+                (passphrase.phrase, rating)
+            }
+            None => {
+                let password = generate_with_policy(args.length, &args.policy)?;
+                let rating = strength::estimate(&password, &[]);
+                (password, rating)
+            }
+        };
+
+        if args.copy {
+            copy(&password)?;
+        } else {
+            println!("{}", password);
+        }
+        // Ratings go to stderr so scripts only read passwords from stdout
+        if args.strength {
+            eprintln!("💪 Strength: {}", styled_strength(&rating));
+        }
+    }
+
+    Ok(())
+}
+
+fn copy(password: &str) -> Result<()> {
+    let storage = get_storage()?;
+    let copied = copy_secret(&storage, password, None, None)?;
+    println!("📋 Password copied to clipboard!");
+    if copied.backend != Backend::Native {
+        println!("📋 Using the {}", copied.backend);
+    }
+    if let Some(seconds) = copied.clear_after {
+        println!("🧹 Clipboard will be cleared in {} seconds.", seconds);
+    }
+    Ok(())
+}
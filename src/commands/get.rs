@@ -1,33 +1,50 @@
-use crate::commands::{ensure_vault_exists, get_master_password, get_storage};
-use console::Style;
-use uuid::Uuid;
+use crate::commands::resolve::resolve_credential;
+use crate::commands::{
+    copy_secret, ensure_vault_exists, get_storage, is_interactive, mask_secret, styled_strength,
+    unlock_password, unlock_vault_quietly,
+};
+use crate::models::credential::Credential;
+use crate::models::item::{ItemDetails, ItemKind};
+use crate::storage::file::FileStorage;
+use crate::utils::clipboard::{Backend, BackendChoice};
+use crate::utils::totp::Totp;
+use anyhow::Result;
+use console::{Key, Style, Term};
 
-pub fn run(query: String, copy: bool) -> anyhow::Result<()> {
+/// How `get` should hand out values besides printing the credential
+pub struct GetArgs {
+    pub copy: bool,
+    pub copy_field: Option<String>,
+    pub copy_login: bool,
+    pub print_field: Option<String>,
+    pub clear_after: Option<u64>,
+    pub clipboard_backend: Option<BackendChoice>,
+    pub reveal: bool,
+}
+
+pub fn run(query: String, args: GetArgs) -> anyhow::Result<()> {
     let storage = get_storage()?;
     ensure_vault_exists(&storage)?;
 
+    // Scripts read the value from stdout, so nothing else may go there
+    if let Some(field) = &args.print_field {
+        let vault = unlock_vault_quietly(&storage)?;
+        let cred = resolve_credential(&vault, &query)?
+            .and_then(|id| vault.find_credential(&id))
+            .ok_or_else(|| anyhow::anyhow!("❌ No credentails found matching: '{}'", query))?;
+        let (value, _) = field_value(cred, field)?;
+        println!("{}", value);
+        return Ok(());
+    }
+
     // Get master password
-    let master_password = get_master_password("🔐 Enter master password to unlock vault:")?;
+    let master_password = unlock_password()?;
 
     // Load vault
     let vault = storage.load_vault(&master_password)?;
 
     // Find credential
-    let credential = if let Ok(uuid) = Uuid::parse_str(&query) {
-        // Search by UUID
-        vault.find_credential(&uuid)
-    } else {
-        // Search by title, username, or URL
-        vault.credentials.iter().find(|c| {
-            c.service.to_lowercase().contains(&query.to_lowercase())
-                || c.username.as_ref().map_or(false, |u| {
-                    u.to_lowercase().contains(&query.to_ascii_lowercase())
-                })
-                || c.url
-                    .as_ref()
-                    .map_or(false, |u| u.to_lowercase().contains(&query.to_lowercase()))
-        })
-    };
+    let credential = resolve_credential(&vault, &query)?.and_then(|id| vault.find_credential(&id));
 
     match credential {
         Some(cred) => {
@@ -43,15 +60,64 @@ pub fn run(query: String, copy: bool) -> anyhow::Result<()> {
 
             println!("{}: {}", lable_style.apply_to("ID"), cred.id);
 
+            if cred.kind() != ItemKind::Login {
+                println!("{}: {}", lable_style.apply_to("Type"), cred.kind());
+            }
+
+            if let Some(folder) = &cred.folder {
+                println!("{}: {}", lable_style.apply_to("Folder"), folder);
+            }
+
             if let Some(username) = &cred.username {
                 println!("{}: {}", lable_style.apply_to("Username"), username);
             }
 
-            println!(
-                "{}: {}",
-                lable_style.apply_to("Password"),
-                password_style.apply_to(&cred.password)
-            );
+            if !cred.password.is_empty() {
+                println!(
+                    "{}: {}",
+                    lable_style.apply_to("Password"),
+                    password_style.apply_to(&cred.password)
+                );
+                println!(
+                    "{}: {}",
+                    lable_style.apply_to("Strength"),
+                    styled_strength(&cred.password_strength())
+                );
+            }
+
+            if let Some(rules) = &cred.password_rules {
+                println!("{}: {}", lable_style.apply_to("Password rules"), rules);
+            }
+
+            match &cred.details {
+                ItemDetails::Card(card) => {
+                    println!("{}: {}", lable_style.apply_to("Brand"), card.brand());
+                }
+                ItemDetails::SshKey(key) => {
+                    if let Some(algorithm) = key.algorithm() {
+                        println!("{}: {}", lable_style.apply_to("Key type"), algorithm);
+                    }
+                }
+                _ => {}
+            }
+            for (label, value, secret) in cred.details.entries() {
+                let shown = if !secret {
+                    value
+                } else if args.reveal {
+                    password_style.apply_to(&value).to_string()
+                } else if let (ItemDetails::Card(card), "Card number") = (&cred.details, label) {
+                    // Last four digits help tell cards apart
+                    card.masked_number()
+                } else {
+                    mask_secret(Some(&value)).to_string()
+                };
+                // Multi-line values (private keys) start on their own line
+                if shown.contains('\n') {
+                    println!("{}:\n{}", lable_style.apply_to(label), shown.trim_end());
+                } else {
+                    println!("{}: {}", lable_style.apply_to(label), shown);
+                }
+            }
 
             if let Some(url) = &cred.url {
                 println!("{}: {}", lable_style.apply_to("URL"), url);
@@ -65,6 +131,33 @@ pub fn run(query: String, copy: bool) -> anyhow::Result<()> {
                 println!("{}: {}", lable_style.apply_to("Tags"), cred.tags.join(", "));
             }
 
+            for field in &cred.fields {
+                if field.concealed && !args.reveal {
+                    println!(
+                        "{}: {}",
+                        lable_style.apply_to(&field.name),
+                        mask_secret(Some(&field.value))
+                    );
+                } else if field.concealed {
+                    println!(
+                        "{}: {}",
+                        lable_style.apply_to(&field.name),
+                        password_style.apply_to(&field.value)
+                    );
+                } else {
+                    println!("{}: {}", lable_style.apply_to(&field.name), field.value);
+                }
+            }
+
+            if !cred.attachments.is_empty() {
+                let names: Vec<_> = cred.attachments.iter().map(|a| a.name.as_str()).collect();
+                println!(
+                    "{}: {}",
+                    lable_style.apply_to("Attachments"),
+                    names.join(", ")
+                );
+            }
+
             println!(
                 "{}: {}",
                 lable_style.apply_to("Created"),
@@ -80,24 +173,19 @@ pub fn run(query: String, copy: bool) -> anyhow::Result<()> {
             }
 
             // Copy to clipboard if requested
-            if copy {
-                #[cfg(feature = "clipboard")]
-                {
-                    match copy_to_clipboard(&cred.password) {
-                        Ok(()) => println!("\n📝 Password copied to clipboard!"),
-                        Err(e) => {
-                            println!("⚠️ Faild to copy to clipboard: {}", e);
-                            println!("Password: {}", cred.password);
-                        }
-                    }
-                }
-
-                #[cfg(not(feature = "clipboard"))]
-                {
-                    println!("\n❌ Clipboard feature not enabled. Password shown above.");
-                    println!("Password: {}", cred.passowrd);
-                }
-            }else {
+            if let Some(field) = &args.copy_field {
+                let (value, secret) = field_value(cred, field)?;
+                copy_value(&storage, field, &value, secret, &args);
+            } else if args.copy_login {
+                copy_login(&storage, cred, &args)?;
+            } else if args.copy {
+                let what = if cred.password.is_empty() {
+                    format!("{} secret", cred.kind())
+                } else {
+                    "Password".to_string()
+                };
+                copy_value(&storage, &what, cred.primary_secret(), true, &args);
+            } else {
                 println!("Password: ........");
                 println!("💡 Use --copy flag to copy to clipboard");
             }
@@ -112,15 +200,131 @@ pub fn run(query: String, copy: bool) -> anyhow::Result<()> {
     Ok(())
 }
 
-#[cfg(feature = "clipboard")]
-fn copy_to_clipboard(content: &str) -> anyhow::Result<()> {
-    use clipboard::{ClipboardContext, ClipboardProvider};
+/// Copy one value and report where it went; secrets are cleared again later
+fn copy_value(storage: &FileStorage, what: &str, value: &str, secret: bool, args: &GetArgs) {
+    // Plain values (username, URL) stay unless --clear-after asks otherwise
+    let clear_after = match secret {
+        true => args.clear_after,
+        false => args.clear_after.or(Some(0)),
+    };
 
-    let mut ctx: ClipboardContext = ClipboardProvider::new()
-        .map_err(|e| anyhow::anyhow!("Faild to initialize Clioboard: {}", e))?;
+    match copy_secret(storage, value, clear_after, args.clipboard_backend) {
+        Ok(copied) => {
+            println!("\n📝 {} copied to clipboard!", what);
+            if copied.backend != Backend::Native {
+                println!("📋 Using the {}", copied.backend);
+            }
+            if let Some(seconds) = copied.clear_after {
+                println!("🧹 Clipboard will be cleared in {} seconds.", seconds);
+            }
+        }
+        Err(e) => {
+            println!("⚠️ Faild to copy to clipboard: {}", e);
+            println!("{}: {}", what, value);
+        }
+    }
+}
 
-    ctx.set_contents(content.to_string())
-        .map_err(|e| anyhow::anyhow!("Faild to set clipboard contents: {}", e))?;
+/// Copy the username, wait for a key, then copy the password (for two-step login forms)
+fn copy_login(storage: &FileStorage, cred: &Credential, args: &GetArgs) -> Result<()> {
+    if cred.primary_secret().is_empty() {
+        println!("❌ '{}' has no password to copy.", cred.service);
+        return Ok(());
+    }
 
+    if let Some(username) = &cred.username {
+        if !is_interactive() {
+            return Err(anyhow::anyhow!(
+                "❌ --copy-login waits for a keypress and needs a terminal"
+            ));
+        }
+        copy_value(storage, "Username", username, false, args);
+        println!("⌨️ Paste it, then press any key to copy the password (Esc to stop)...");
+        if Term::stderr().read_key()? == Key::Escape {
+            println!("❌ Password not copied.");
+            return Ok(());
+        }
+    }
+
+    copy_value(storage, "Password", cred.primary_secret(), true, args);
     Ok(())
 }
+
+/// Value of a named field and whether it is secret. Looks at the built-in
+/// fields, `otp` (the current code), custom fields, then typed-item details
+/// such as `cvv`; names match ignoring case, spaces and dashes.
+fn field_value(cred: &Credential, name: &str) -> Result<(String, bool)> {
+    let key = field_key(name);
+    let builtin = match key.as_str() {
+        "password" => Some(cred.primary_secret().to_string())
+            .filter(|v| !v.is_empty())
+            .map(|v| (v, true)),
+        "username" | "user" => cred.username.clone().map(|v| (v, false)),
+        "url" => cred.url.clone().map(|v| (v, false)),
+        "notes" => cred.notes.clone().map(|v| (v, false)),
+        "service" | "title" => Some((cred.service.clone(), false)),
+        "otp" | "totp" => match cred.otp_field() {
+            Some(field) => Some((Totp::parse(&field.value)?.now().0, true)),
+            None => None,
+        },
+        _ => None,
+    };
+
+    builtin
+        .or_else(|| {
+            cred.fields
+                .iter()
+                .find(|f| field_key(&f.name) == key)
+                .map(|f| (f.value.clone(), f.concealed))
+        })
+        .or_else(|| {
+            cred.details
+                .entries()
+                .into_iter()
+                .find(|(label, _, _)| field_key(label) == key)
+                .map(|(_, value, secret)| (value, secret))
+        })
+        .ok_or_else(|| anyhow::anyhow!("❌ '{}' has no field '{}'", cred.service, name))
+}
+
+fn field_key(name: &str) -> String {
+    name.chars()
+        .filter(|c| c.is_alphanumeric())
+        .flat_map(char::to_lowercase)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::item::CardDetails;
+
+    #[test]
+    fn test_field_value() {
+        let mut cred = Credential::new("GitLab".to_string(), "hunter2".to_string())
+            .with_username("bob".to_string());
+        cred.set_field("Recovery Code".to_string(), "r-123".to_string(), true);
+        let seed = "GEZDGNBVGY3TQOJQGEZDGNBVGY3TQOJQ".to_string();
+        cred.set_field("otp".to_string(), seed, true);
+
+        let password = field_value(&cred, "password").unwrap();
+        assert_eq!(password, ("hunter2".to_string(), true));
+        let username = field_value(&cred, "USERNAME").unwrap();
+        assert_eq!(username, ("bob".to_string(), false));
+        assert_eq!(field_value(&cred, "recovery-code").unwrap().0, "r-123");
+        let (code, secret) = field_value(&cred, "otp").unwrap();
+        assert!(secret && code.len() == 6 && code.chars().all(|c| c.is_ascii_digit()));
+        assert!(field_value(&cred, "url").is_err());
+
+        let card = CardDetails::new(None, "4111 1111 1111 1111", "12/30", "123").unwrap();
+        let card = Credential::with_details("Visa".to_string(), ItemDetails::Card(card));
+        assert_eq!(
+            field_value(&card, "cvv").unwrap(),
+            ("123".to_string(), true)
+        );
+        assert_eq!(
+            field_value(&card, "password").unwrap().0,
+            "4111111111111111"
+        );
+    }
+}
@@ -0,0 +1,178 @@
+use crate::commands::{ensure_vault_exists, get_master_password, get_storage};
+use crate::models::merge::{last_writer_wins, merge_vaults};
+use crate::storage::file::{FileStorage, VaultFile};
+use crate::storage::git::{Divergence, GitRepo};
+use anyhow::Result;
+
+/// Turn the vault directory into a git repository
+pub fn init(remote: Option<String>) -> Result<()> {
+    let storage = get_storage()?;
+    ensure_vault_exists(&storage)?;
+
+    let repo = match storage.git_repo() {
+        Some(repo) => {
+            println!("ℹ️ Vault directory is already a git repository.");
+            repo
+        }
+        None => GitRepo::init(storage.vault_dir())?,
+    };
+
+    if let Some(url) = remote {
+        repo.set_remote(&url)?;
+        println!("🔗 Remote set to: {}", url);
+    }
+
+    repo.commit_all("Initial vault commit")?;
+
+    println!("✅ Vault is now versioned with git!");
+    println!("📍 Repository: {}", storage.vault_dir().display());
+    println!("💡 Every save is committed automatically. Share it with: pwdmgr git push");
+
+    Ok(())
+}
+
+/// Commit any pending changes in the vault directory
+pub fn commit(message: Option<String>) -> Result<()> {
+    let storage = get_storage()?;
+    let repo = ensure_repo(&storage)?;
+
+    let message = message.unwrap_or_else(|| "Update vault".to_string());
+    if repo.commit_all(&message)? {
+        println!("✅ Changes committed.");
+    } else {
+        println!("ℹ️ Nothing to commit, vault history is up to date.");
+    }
+
+    Ok(())
+}
+
+/// Push vault history to the remote
+pub fn push() -> Result<()> {
+    let storage = get_storage()?;
+    let repo = ensure_repo(&storage)?;
+    ensure_remote(&repo)?;
+
+    repo.commit_all("Update vault")?;
+    repo.push()?;
+
+    println!("⬆️ Vault pushed successfully!");
+    Ok(())
+}
+
+/// Pull vault history, merging diverged vaults credential by credential
+pub fn pull() -> Result<()> {
+    let storage = get_storage()?;
+    let repo = ensure_repo(&storage)?;
+    ensure_remote(&repo)?;
+
+    repo.commit_all("Update vault")?;
+
+    let upstream = repo.upstream_ref()?;
+    match repo.fetch()? {
+        Divergence::NoUpstream => {
+            println!("ℹ️ Remote has no vault history yet. Use: pwdmgr git push");
+        }
+        Divergence::UpToDate => {
+            println!("✅ Vault is already up to date.");
+        }
+        Divergence::FastForward => {
+            repo.fast_forward(&upstream)?;
+            println!("⬇️ Vault updated from remote.");
+        }
+        Divergence::Diverged => {
+            println!("🔀 Local and remote vaults have diverged, merging credentials...");
+            merge_diverged(&storage, &repo, &upstream)?;
+        }
+    }
+
+    Ok(())
+}
+
+/// Show recent vault history
+pub fn log(limit: usize) -> Result<()> {
+    let storage = get_storage()?;
+    let repo = ensure_repo(&storage)?;
+
+    let entries = repo.log(limit)?;
+    if entries.is_empty() {
+        println!("📭 No vault history yet.");
+        return Ok(());
+    }
+
+    println!("📜 Vault history:");
+    for entry in entries {
+        println!("  {}", entry);
+    }
+
+    Ok(())
+}
+
+/// Decrypt both sides (and their common ancestor) and merge by credential id,
+/// so the encrypted blob itself never ends up with git conflict markers.
+fn merge_diverged(storage: &FileStorage, repo: &GitRepo, upstream: &str) -> Result<()> {
+    let vault_name = storage.vault_file_name();
+    let read_revision = |rev: &str| -> Result<VaultFile> {
+        let data = repo.show_file(rev, &vault_name)?;
+        Ok(serde_json::from_slice(&data)?)
+    };
+
+    let master_password = get_master_password("🔐 Enter master password to merge vaults:")?;
+
+    let ours = storage.load_vault(&master_password)?;
+    let theirs = FileStorage::decrypt_vault_file(&read_revision(upstream)?, &master_password)
+        .map_err(|_| {
+            anyhow::anyhow!("❌ Could not decrypt remote vault with this master password")
+        })?;
+
+    // The ancestor may predate a master password change, then fall back to a union merge
+    let base = repo
+        .merge_base("HEAD", upstream)
+        .and_then(|rev| read_revision(&rev).ok())
+        .and_then(|file| FileStorage::decrypt_vault_file(&file, &master_password).ok());
+
+    let (merged, report) = merge_vaults(base.as_ref(), &ours, &theirs, last_writer_wins);
+
+    repo.begin_merge(upstream)?;
+    let result = storage
+        .write_vault(&merged, &master_password)
+        .and_then(|_| repo.commit_all("Merge remote vault changes"));
+
+    if let Err(e) = result {
+        let _ = repo.abort_merge();
+        return Err(e);
+    }
+
+    println!("✅ Vaults merged successfully!");
+    println!(
+        "📊 {} added, {} updated, {} removed",
+        report.added, report.updated, report.removed
+    );
+    if report.conflicts > 0 {
+        println!(
+            "⚠️ {} credential(s) were edited on both sides, kept the most recently updated version",
+            report.conflicts
+        );
+    }
+    println!("💡 Share the merged vault with: pwdmgr git push");
+
+    Ok(())
+}
+
+fn ensure_repo(storage: &FileStorage) -> Result<GitRepo> {
+    ensure_vault_exists(storage)?;
+
+    storage.git_repo().ok_or_else(|| {
+        println!("❌ Vault is not under version control.");
+        println!("💡 Enable history with: pwdmgr git init");
+        anyhow::anyhow!("Vault directory is not a git repository")
+    })
+}
+
+fn ensure_remote(repo: &GitRepo) -> Result<()> {
+    if repo.remote_url().is_none() {
+        println!("❌ No git remote configured.");
+        println!("💡 Add one with: pwdmgr git init --remote <url>");
+        return Err(anyhow::anyhow!("No git remote configured"));
+    }
+    Ok(())
+}
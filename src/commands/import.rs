@@ -0,0 +1,92 @@
+use crate::commands::{ensure_vault_exists, get_master_password, get_storage};
+use crate::models::folder;
+use crate::storage::import::{self, ImportFormat};
+use anyhow::Result;
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::PathBuf;
+
+/// Import credentials from another password manager's export file
+pub fn run(file: PathBuf, format: Option<ImportFormat>, into: Option<String>) -> Result<()> {
+    let format = match format.or_else(|| ImportFormat::detect(&file)) {
+        Some(format) => format,
+        None => {
+            return Err(anyhow::anyhow!(
+                "❌ Can't tell the format of {}, pass --format keepass-csv or bitwarden-json",
+                file.display()
+            ));
+        }
+    };
+    let into = match into {
+        Some(into) => folder::normalize(&into)?,
+        None => None,
+    };
+
+    let data = fs::read_to_string(&file)
+        .map_err(|e| anyhow::anyhow!("❌ Could not read {}: {}", file.display(), e))?;
+    let imported = import::parse(format, &data)?;
+
+    let storage = get_storage()?;
+    ensure_vault_exists(&storage)?;
+
+    let master_password = get_master_password("🔐 Enter master password to unlock vault:")?;
+    let mut vault = storage.load_vault(&master_password)?;
+
+    println!(
+        "📥 Importing {} entries from {}",
+        imported.credentials.len(),
+        file.display()
+    );
+
+    let mut added = 0;
+    let mut skipped = 0;
+    let mut per_folder: BTreeMap<String, usize> = BTreeMap::new();
+
+    for mut credential in imported.credentials {
+        credential.folder = match (&into, credential.folder.take()) {
+            (Some(into), Some(folder)) => Some(format!("{}/{}", into, folder)),
+            (into, folder) => folder.or_else(|| into.clone()),
+        };
+
+        // Re-running an import shouldn't duplicate everything
+        let duplicate = vault.credentials.iter().any(|existing| {
+            existing.service == credential.service
+                && existing.username == credential.username
+                && existing.folder == credential.folder
+        });
+        if duplicate {
+            skipped += 1;
+            continue;
+        }
+
+        let folder_name = credential.folder.clone().unwrap_or_else(|| "/".to_string());
+        *per_folder.entry(folder_name).or_default() += 1;
+        vault.add_credentail(credential);
+        added += 1;
+    }
+
+    for warning in &imported.warnings {
+        println!("⚠️ {}", warning);
+    }
+
+    if added == 0 {
+        println!("📭 Nothing new to import ({} duplicates skipped).", skipped);
+        return Ok(());
+    }
+
+    storage.save_vault(&vault, &master_password)?;
+
+    for (folder, count) in &per_folder {
+        println!("  📁 {} ({})", folder, count);
+    }
+    println!(
+        "✅ Imported {} credentials ({} duplicates skipped)",
+        added, skipped
+    );
+    println!(
+        "💡 The export file holds plaintext passwords, delete it: {}",
+        file.display()
+    );
+
+    Ok(())
+}
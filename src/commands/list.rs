@@ -1,13 +1,158 @@
-use crate::commands::{ensure_vault_exists, get_master_password, get_storage};
+use crate::commands::{ensure_vault_exists, get_storage, print_paged, unlock_password};
+use crate::models::credential::Credential;
+use crate::models::folder;
+use crate::models::item::ItemKind;
+use crate::models::query::Query;
+use crate::utils::Utc;
 use anyhow::Result;
-use console::{Style, Term};
+use console::Style;
+use prettytable::{Cell, Row, Table, format};
+use std::collections::BTreeMap;
+use std::fmt::Write;
+use std::str::FromStr;
+
+/// Longest cell printed in table mode before it is cut off
+const MAX_CELL_WIDTH: usize = 40;
+
+/// Columns shown by `--table` when `--columns` is not given
+const DEFAULT_COLUMNS: [Column; 6] = [
+    Column::Service,
+    Column::Username,
+    Column::Url,
+    Column::Folder,
+    Column::Tags,
+    Column::Updated,
+];
+
+/// Options for `pwdmgr list`
+pub struct ListArgs {
+    pub show_password: bool,
+    pub kind: Option<ItemKind>,
+    pub folder: Option<String>,
+    pub recursive: bool,
+    pub tree: bool,
+    pub filter: Option<String>,
+    pub sort: Option<SortKey>,
+    pub reverse: bool,
+    pub limit: Option<usize>,
+    pub offset: usize,
+    pub table: bool,
+    pub columns: Option<Vec<Column>>,
+    pub no_pager: bool,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SortKey {
+    Service,
+    Username,
+    Created,
+    Updated,
+}
+
+impl FromStr for SortKey {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "service" | "title" | "name" => Ok(SortKey::Service),
+            "username" | "user" => Ok(SortKey::Username),
+            "created" => Ok(SortKey::Created),
+            "updated" | "modified" => Ok(SortKey::Updated),
+            _ => Err(anyhow::anyhow!(
+                "❌ Unknown sort key '{}'. Use service, username, created or updated",
+                s
+            )),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Column {
+    Service,
+    Username,
+    Url,
+    Folder,
+    Type,
+    Tags,
+    Created,
+    Updated,
+    Id,
+    Password,
+}
+
+impl Column {
+    fn title(self) -> &'static str {
+        match self {
+            Column::Service => "SERVICE",
+            Column::Username => "USERNAME",
+            Column::Url => "URL",
+            Column::Folder => "FOLDER",
+            Column::Type => "TYPE",
+            Column::Tags => "TAGS",
+            Column::Created => "CREATED",
+            Column::Updated => "UPDATED",
+            Column::Id => "ID",
+            Column::Password => "PASSWORD",
+        }
+    }
+
+    fn value(self, credential: &Credential, show_password: bool) -> String {
+        match self {
+            Column::Service => credential.service.clone(),
+            Column::Username => credential.username.clone().unwrap_or_default(),
+            Column::Url => credential.url.clone().unwrap_or_default(),
+            Column::Folder => credential.folder.clone().unwrap_or_default(),
+            Column::Type => credential.kind().to_string(),
+            Column::Tags => credential.tags.join(", "),
+            Column::Created => credential.created_at.format("%Y-%m-%d").to_string(),
+            Column::Updated => credential.updated_at.format("%Y-%m-%d").to_string(),
+            Column::Id => credential.id.to_string(),
+            Column::Password => match credential.primary_secret() {
+                "" => String::new(),
+                secret if show_password => secret.to_string(),
+                _ => "........".to_string(),
+            },
+        }
+    }
+}
+
+impl FromStr for Column {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.trim().to_lowercase().as_str() {
+            "service" | "title" | "name" => Ok(Column::Service),
+            "username" | "user" => Ok(Column::Username),
+            "url" => Ok(Column::Url),
+            "folder" => Ok(Column::Folder),
+            "type" | "kind" => Ok(Column::Type),
+            "tags" | "tag" => Ok(Column::Tags),
+            "created" => Ok(Column::Created),
+            "updated" | "modified" => Ok(Column::Updated),
+            "id" => Ok(Column::Id),
+            "password" | "secret" => Ok(Column::Password),
+            _ => Err(anyhow::anyhow!(
+                "❌ Unknown column '{}'. Use service, username, url, folder, type, tags, created, updated, id or password",
+                s
+            )),
+        }
+    }
+}
+
+pub fn run(args: ListArgs) -> Result<()> {
+    let folder = match &args.folder {
+        Some(folder) => Some(folder::normalize(folder)?),
+        None => None,
+    };
+    let filter = args.filter.as_deref().map(Query::parse).transpose()?;
+    let now = Utc::now();
+    let show_password = args.show_password;
 
-pub fn run(show_password: bool) -> Result<()> {
     let storage = get_storage()?;
     ensure_vault_exists(&storage)?;
 
     // Get master password
-    let master_password = get_master_password("🔐 Enter master password to unlock vault:")?;
+    let master_password = unlock_password()?;
 
     // Load Vault
     let vault = storage.load_vault(&master_password)?;
@@ -18,73 +163,355 @@ pub fn run(show_password: bool) -> Result<()> {
         return Ok(());
     }
 
-    // Display credentials
-    println!("📝 Credentials in vault ({}):\n", vault.credentials.len());
+    let mut credentials: Vec<_> = vault
+        .credentials
+        .iter()
+        .filter(|c| args.kind.is_none_or(|k| c.kind() == k))
+        .filter(|c| match &folder {
+            // Without --folder everything is listed; the tree starts at the root
+            None => true,
+            Some(parent) => folder::contains(
+                parent.as_deref(),
+                c.folder.as_deref(),
+                args.recursive || args.tree,
+            ),
+        })
+        .filter(|c| filter.as_ref().is_none_or(|q| q.matches(c, now)))
+        .collect();
+
+    if credentials.is_empty() {
+        match (&folder, args.kind) {
+            _ if filter.is_some() => println!("📭 No credentials match the filter."),
+            (Some(parent), _) => println!(
+                "📭 No credentials found in folder '{}'.",
+                parent.as_deref().unwrap_or("/")
+            ),
+            (None, Some(kind)) => println!("📭 No {} items found in vault.", kind),
+            (None, None) => {}
+        }
+        return Ok(());
+    }
+
+    if args.tree {
+        let root = folder.flatten();
+        return show(&render_tree(&credentials, root.as_deref()), args.no_pager);
+    }
+
+    // Sort first so --offset/--limit page through a stable order
+    if let Some(key) = args.sort {
+        sort_credentials(&mut credentials, key);
+    }
+    if args.reverse {
+        credentials.reverse();
+    }
+    let total = credentials.len();
+    let page = page(&credentials, args.offset, args.limit);
+
+    if page.is_empty() {
+        println!(
+            "📭 Nothing to show at offset {} ({} credentials matched).",
+            args.offset, total
+        );
+        return Ok(());
+    }
+
+    let mut output = String::new();
+    if args.table || args.columns.is_some() {
+        let columns = args.columns.as_deref().unwrap_or(&DEFAULT_COLUMNS);
+        output.push_str(&render_table(page, columns, show_password));
+    } else {
+        writeln!(output, "📝 Credentials in vault ({}):\n", total)?;
+        render_blocks(&mut output, page, args.offset, show_password)?;
+        if !show_password {
+            writeln!(output, "💡 Use --show-password flag to reveal passwords")?;
+        }
+    }
+
+    if page.len() < total {
+        writeln!(
+            output,
+            "📄 Showing {}-{} of {} (use --offset/--limit to page)",
+            args.offset + 1,
+            args.offset + page.len(),
+            total
+        )?;
+    }
+
+    show(&output, args.no_pager)
+}
+
+fn show(output: &str, no_pager: bool) -> Result<()> {
+    if no_pager {
+        print!("{}", output);
+        Ok(())
+    } else {
+        print_paged(output)
+    }
+}
+
+/// Stable sort so equal keys keep their vault order
+fn sort_credentials(credentials: &mut [&Credential], key: SortKey) {
+    match key {
+        SortKey::Service => credentials.sort_by_key(|c| c.service.to_lowercase()),
+        // Entries without a username go last
+        SortKey::Username => credentials.sort_by_key(|c| {
+            (
+                c.username.is_none(),
+                c.username.as_deref().map(str::to_lowercase),
+            )
+        }),
+        SortKey::Created => credentials.sort_by_key(|c| c.created_at),
+        SortKey::Updated => credentials.sort_by_key(|c| c.updated_at),
+    }
+}
+
+fn page<T>(items: &[T], offset: usize, limit: Option<usize>) -> &[T] {
+    let start = offset.min(items.len());
+    let end = match limit {
+        Some(limit) => start.saturating_add(limit).min(items.len()),
+        None => items.len(),
+    };
+    &items[start..end]
+}
+
+/// Cut a cell down to `max` characters, marking the cut with an ellipsis
+fn truncate(text: &str, max: usize) -> String {
+    let text = text.lines().next().unwrap_or_default();
+    if text.chars().count() <= max {
+        text.to_string()
+    } else {
+        let mut cut: String = text.chars().take(max - 1).collect();
+        cut.push('…');
+        cut
+    }
+}
+
+/// One line per credential
+fn render_table(credentials: &[&Credential], columns: &[Column], show_password: bool) -> String {
+    let mut table = Table::new();
+    table.set_format(*format::consts::FORMAT_CLEAN);
+    table.set_titles(Row::new(
+        columns.iter().map(|c| Cell::new(c.title())).collect(),
+    ));
+
+    for credential in credentials {
+        table.add_row(Row::new(
+            columns
+                .iter()
+                .map(|column| {
+                    let value = column.value(credential, show_password);
+                    // IDs are only useful whole
+                    if *column == Column::Id {
+                        Cell::new(&value)
+                    } else {
+                        Cell::new(&truncate(&value, MAX_CELL_WIDTH))
+                    }
+                })
+                .collect(),
+        ));
+    }
+
+    table.to_string()
+}
 
-    let _term = Term::stdout();
+/// The detailed multi-line view, numbered from `offset + 1`
+fn render_blocks(
+    out: &mut String,
+    credentials: &[&Credential],
+    offset: usize,
+    show_password: bool,
+) -> std::fmt::Result {
     let header_style = Style::new().bold().cyan();
     let id_style = Style::new().dim();
-    let _title_style = Style::new().bold();
     let warning_style = Style::new().red();
 
-    for (index, credential) in vault.credentials.iter().enumerate() {
-        println!(
+    for (index, credential) in credentials.iter().enumerate() {
+        writeln!(
+            out,
             "{}",
-            header_style.apply_to(format!("{}. {}", index + 1, credential.service))
-        );
-        println!(
+            header_style.apply_to(format!("{}. {}", offset + index + 1, credential.service))
+        )?;
+        if credential.kind() != ItemKind::Login {
+            writeln!(out, "  Type: {}", credential.kind())?;
+        }
+        if let Some(folder) = &credential.folder {
+            writeln!(out, "  Folder: {}", folder)?;
+        }
+        writeln!(
+            out,
             "  {}: {}",
             id_style.apply_to("ID"),
             id_style.apply_to(credential.id)
-        );
+        )?;
 
         if let Some(username) = &credential.username {
-            println!("  Username: {}", username);
+            writeln!(out, "  Username: {}", username)?;
         }
 
         if let Some(url) = &credential.url {
-            println!("  URL: {}", url);
+            writeln!(out, "  URL: {}", url)?;
         }
 
-        if show_password {
-            println!(
+        if credential.password.is_empty() {
+            // Typed items keep their secrets in the details below
+        } else if show_password {
+            writeln!(
+                out,
                 "  {}: {}",
                 warning_style.apply_to("Password"),
                 warning_style.apply_to(&credential.password)
-            );
+            )?;
         } else {
-            println!(
-                "  Password: {} (use --show-passwords to reveal)",
-                "........"
-            );
+            writeln!(out, "  Password: ........ (use --show-passwords to reveal)")?;
         }
 
         if let Some(notes) = &credential.notes {
-            println!("  Notes: {}", notes);
+            writeln!(out, "  Notes: {}", notes)?;
+        }
+
+        for (label, value, secret) in credential.details.entries() {
+            if secret && !show_password {
+                writeln!(out, "  {}: ........", label)?;
+            } else if !value.contains('\n') {
+                writeln!(out, "  {}: {}", label, value)?;
+            }
+        }
+
+        for field in &credential.fields {
+            if field.concealed && !show_password {
+                writeln!(out, "  {}: ........", field.name)?;
+            } else {
+                writeln!(out, "  {}: {}", field.name, field.value)?;
+            }
         }
 
         if !credential.tags.is_empty() {
-            println!("  Tags: {}", credential.tags.join(",  "));
+            writeln!(out, "  Tags: {}", credential.tags.join(",  "))?;
         }
 
-        println!(
+        writeln!(
+            out,
             "  Created: {}",
-            credential.created_at.format("%Y-%m-%%d %H:%M UTC")
-        );
+            credential.created_at.format("%Y-%m-%d %H:%M UTC")
+        )?;
 
         if credential.created_at != credential.updated_at {
-            println!(
+            writeln!(
+                out,
                 "  Updated: {}",
-                credential.updated_at.format("%Y-%m-%%d %H:%M UTC")
+                credential.updated_at.format("%Y-%m-%d %H:%M UTC")
+            )?;
+        }
+
+        writeln!(out)?;
+    }
+
+    Ok(())
+}
+
+/// Credentials grouped by folder, indented by depth below `root`
+fn render_tree(credentials: &[&Credential], root: Option<&str>) -> String {
+    let folder_style = Style::new().bold().cyan();
+    let dim = Style::new().dim();
+    let root_depth = folder::segments(root).len();
+    let mut out = String::new();
+
+    // Keyed by path segments so subfolders sort directly after their parent
+    let mut folders: BTreeMap<Vec<&str>, Vec<&Credential>> = BTreeMap::new();
+    for credential in credentials {
+        let segments = folder::segments(credential.folder.as_deref());
+        for depth in root_depth..segments.len() {
+            folders.entry(segments[..depth].to_vec()).or_default();
+        }
+        folders.entry(segments).or_default().push(credential);
+    }
+
+    // Writing into a String cannot fail
+    let _ = writeln!(
+        out,
+        "{}",
+        folder_style.apply_to(format!("📁 {}", root.unwrap_or("/")))
+    );
+    for (path, entries) in folders.iter_mut() {
+        let depth = path.len() - root_depth;
+        if depth > 0 {
+            let total = credentials
+                .iter()
+                .filter(|c| folder::segments(c.folder.as_deref()).starts_with(path))
+                .count();
+            let _ = writeln!(
+                out,
+                "{}{} {}",
+                "  ".repeat(depth),
+                folder_style.apply_to(format!("📁 {}/", path[path.len() - 1])),
+                dim.apply_to(format!("({})", total))
             );
         }
 
-        println!();
+        entries.sort_by_key(|c| c.service.to_lowercase());
+        for credential in entries.iter() {
+            let _ = match &credential.username {
+                Some(username) => writeln!(
+                    out,
+                    "{}🔑 {} {}",
+                    "  ".repeat(depth + 1),
+                    credential.service,
+                    dim.apply_to(format!("({})", username))
+                ),
+                None => writeln!(out, "{}🔑 {}", "  ".repeat(depth + 1), credential.service),
+            };
+        }
     }
 
-    if !show_password {
-        println!("💡 Use --show-password flag to reveal passwords");
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn credential(service: &str, username: Option<&str>) -> Credential {
+        let mut credential = Credential::new(service.to_string(), "pw".to_string());
+        credential.username = username.map(str::to_string);
+        credential
     }
 
-    Ok(())
+    #[test]
+    fn test_sort_and_page() {
+        let a = credential("beta", Some("zed"));
+        let b = credential("Alpha", None);
+        let c = credential("gamma", Some("amy"));
+        let mut list = vec![&a, &b, &c];
+
+        sort_credentials(&mut list, SortKey::Service);
+        let names: Vec<_> = list.iter().map(|c| c.service.as_str()).collect();
+        assert_eq!(names, ["Alpha", "beta", "gamma"]);
+
+        // Missing usernames sort last
+        sort_credentials(&mut list, SortKey::Username);
+        let names: Vec<_> = list.iter().map(|c| c.service.as_str()).collect();
+        assert_eq!(names, ["gamma", "beta", "Alpha"]);
+
+        assert_eq!(page(&list, 1, Some(1)).len(), 1);
+        assert_eq!(page(&list, 1, None).len(), 2);
+        assert!(page(&list, 5, Some(2)).is_empty());
+    }
+
+    #[test]
+    fn test_table_columns() {
+        let mut long = credential(&"x".repeat(60), Some("bob"));
+        long.tags = vec!["work".to_string(), "prod".to_string()];
+        let columns: Vec<Column> = "service, tags,password"
+            .split(',')
+            .map(|c| c.parse().unwrap())
+            .collect();
+
+        let table = render_table(&[&long], &columns, false);
+        assert!(table.contains("SERVICE"));
+        assert!(table.contains("work, prod"));
+        assert!(table.contains(&format!("{}…", "x".repeat(MAX_CELL_WIDTH - 1))));
+        assert!(!table.contains("pw "));
+        assert!(table.contains("........"));
+        assert!("colour".parse::<Column>().is_err());
+    }
 }
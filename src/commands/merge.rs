@@ -0,0 +1,109 @@
+use crate::commands::diff::describe_field;
+use crate::commands::{
+    ensure_vault_exists, get_master_password, get_storage, is_interactive, load_other_vault,
+    warn_missing_attachments,
+};
+use crate::models::credential::Credential;
+use crate::models::merge::{Resolution, changed_fields, last_writer_wins, merge_vaults};
+use crate::storage::file::FileStorage;
+use anyhow::Result;
+use console::Style;
+use dialoguer::Select;
+use std::path::PathBuf;
+
+pub fn run(other: PathBuf) -> Result<()> {
+    let storage = get_storage()?;
+    ensure_vault_exists(&storage)?;
+
+    let master_password = get_master_password("🔐 Enter master password to unlock vault:")?;
+    let vault = storage.load_vault(&master_password)?;
+    let other_vault = load_other_vault(&other, &master_password)?;
+
+    println!("🔀 Merging credentials from: {}", other.display());
+
+    let interactive = is_interactive();
+    let mut prompt_error = None;
+    let (merged, report) = merge_vaults(None, &vault, &other_vault, |ours, theirs| {
+        if !interactive || prompt_error.is_some() {
+            return last_writer_wins(ours, theirs);
+        }
+        resolve_conflict(ours, theirs).unwrap_or_else(|e| {
+            prompt_error = Some(e);
+            last_writer_wins(ours, theirs)
+        })
+    });
+
+    if let Some(e) = prompt_error {
+        println!("❌ Merge cancelled, local vault left unchanged.");
+        return Err(e);
+    }
+
+    // Attachment files of the other vault sit beside it, not in the vault file
+    let store = storage.attachments();
+    let other_store = FileStorage::from_path(other.clone()).attachments();
+    for (_, attachment) in store.missing(&merged) {
+        store.copy_from(&other_store, attachment)?;
+    }
+
+    if report.added == 0 && report.updated == 0 {
+        println!("✅ Local vault already contains everything from the other vault.");
+        warn_missing_attachments(&storage, &merged);
+        return Ok(());
+    }
+
+    storage.save_vault(&merged, &master_password)?;
+
+    println!("✅ Vaults merged successfully!");
+    println!(
+        "📊 {} added, {} updated, {} conflict(s)",
+        report.added, report.updated, report.conflicts
+    );
+    if report.conflicts > 0 && !interactive {
+        println!("⚠️ Conflicts were resolved by keeping the most recently updated version.");
+    }
+    warn_missing_attachments(&storage, &merged);
+
+    Ok(())
+}
+
+/// Ask the user which version of a credential edited on both sides to keep
+fn resolve_conflict(ours: &Credential, theirs: &Credential) -> Result<Resolution> {
+    let title_style = Style::new().bold().yellow();
+
+    println!();
+    println!(
+        "{}",
+        title_style.apply_to(format!("⚠️ Conflict: {} ({})", ours.service, ours.id))
+    );
+    for field in changed_fields(ours, theirs) {
+        println!("    {}", describe_field(&field));
+    }
+
+    let options = [
+        format!(
+            "Keep local version (updated {})",
+            ours.updated_at.format("%Y-%m-%d %H:%M UTC")
+        ),
+        format!(
+            "Take other version (updated {})",
+            theirs.updated_at.format("%Y-%m-%d %H:%M UTC")
+        ),
+        "Keep both".to_string(),
+    ];
+    let default = match last_writer_wins(ours, theirs) {
+        Resolution::TakeTheirs => 1,
+        _ => 0,
+    };
+
+    let choice = Select::new()
+        .with_prompt("How should this conflict be resolved?")
+        .items(&options)
+        .default(default)
+        .interact()?;
+
+    Ok(match choice {
+        0 => Resolution::KeepOurs,
+        1 => Resolution::TakeTheirs,
+        _ => Resolution::KeepBoth,
+    })
+}
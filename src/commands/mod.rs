@@ -1,40 +1,307 @@
 pub mod add;
+pub mod attach;
+pub mod audit;
+pub mod breach_check;
 pub mod change_password;
 pub mod delete;
+pub mod diff;
+pub mod edit;
+pub mod generate;
 pub mod get;
+pub mod git;
+pub mod import;
 pub mod init;
 pub mod list;
+pub mod merge;
+pub mod mv;
+pub mod resolve;
 pub mod search;
+pub mod shell;
 pub mod status;
+pub mod sync;
+pub mod tag;
+pub mod tui;
 
+use crate::models::credential::{Credential, CredentialVault};
+use crate::storage::attachments::ATTACHMENTS_DIR;
+use crate::storage::config::Config;
 use crate::storage::file::FileStorage;
+use crate::utils::clipboard::{self, Backend, BackendChoice, DEFAULT_CLEAR_AFTER};
+use crate::utils::hibp::HashFile;
+use crate::utils::strength::Strength;
 use anyhow::Result;
-use rpassword;
+use console::Style;
+use std::io::{IsTerminal, Write};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::sync::Mutex;
+use zeroize::Zeroize;
 
 /// Common utility: Get master password from user securely
 pub fn get_master_password(prompt: &str) -> Result<String> {
     println!("{}", prompt);
     let password = rpassword::read_password()?;
 
-    if password.trim().is_empty(){
+    if password.trim().is_empty() {
         return Err(anyhow::anyhow!("❌ Password cannot be empty"));
     }
 
     Ok(password)
 }
 
+/// Master password held by `pwdmgr shell` while its session is unlocked
+static SESSION_PASSWORD: Mutex<Option<String>> = Mutex::new(None);
+
+/// Common utility: Get the master password to unlock the vault, reusing the
+/// shell session's password instead of prompting when one is active
+pub fn unlock_password() -> Result<String> {
+    if let Some(password) = SESSION_PASSWORD.lock().unwrap().as_ref() {
+        return Ok(password.clone());
+    }
+    get_master_password("🔐 Enter master password to unlock vault:")
+}
+
+/// Common utility: Unlock the vault without writing anything to stdout, for
+/// output meant to be piped (the prompt goes to stderr instead)
+pub fn unlock_vault_quietly(storage: &FileStorage) -> Result<CredentialVault> {
+    let password = match SESSION_PASSWORD.lock().unwrap().clone() {
+        Some(password) => password,
+        None => {
+            eprintln!("🔐 Enter master password to unlock vault:");
+            let password = rpassword::read_password()?;
+            if password.trim().is_empty() {
+                return Err(anyhow::anyhow!("❌ Password cannot be empty"));
+            }
+            password
+        }
+    };
+    FileStorage::decrypt_vault_file(&storage.load_vault_file()?, &password)
+}
+
+/// Common utility: Start (`Some`) or end (`None`) a shell session, wiping the old password
+pub fn set_session_password(password: Option<String>) {
+    let mut session = SESSION_PASSWORD.lock().unwrap();
+    if let Some(old) = session.as_mut() {
+        old.zeroize();
+    }
+    *session = password;
+}
+
 /// Common utility: Get Storage instance
-pub fn get_storage()-> Result<FileStorage>{
+pub fn get_storage() -> Result<FileStorage> {
     FileStorage::new()
 }
 
 /// Common utility: Check if Vault exists and guide user
-pub fn ensure_vault_exists(storage: &FileStorage) ->Result<()>{
-    if !storage.vault_exists(){
+pub fn ensure_vault_exists(storage: &FileStorage) -> Result<()> {
+    if !storage.vault_exists() {
         println!("❌ No vault found.");
         println!("💡 Initialize a new vault with: pwdmgr init");
         return Err(anyhow::anyhow!("Vault not found"));
     }
 
     Ok(())
-}
\ No newline at end of file
+}
+
+/// Common utility: Check if we can prompt the user interactively
+pub fn is_interactive() -> bool {
+    std::io::stdin().is_terminal() && console::user_attended_stderr()
+}
+
+/// Common utility: Decrypt another vault file, trying the current master
+/// password first and asking for that vault's own password if it differs
+pub fn load_other_vault(path: &Path, master_password: &str) -> Result<CredentialVault> {
+    let other = FileStorage::from_path(path.to_path_buf());
+    if !other.vault_exists() {
+        return Err(anyhow::anyhow!(
+            "❌ No vault file found at {}",
+            path.display()
+        ));
+    }
+
+    let vault_file = other.load_vault_file()?;
+    match FileStorage::decrypt_vault_file(&vault_file, master_password) {
+        Ok(vault) => Ok(vault),
+        Err(_) => {
+            let other_password =
+                get_master_password("🔐 Enter master password for the other vault:")?;
+            FileStorage::decrypt_vault_file(&vault_file, &other_password)
+        }
+    }
+}
+
+/// Warn about attachments whose encrypted file is not in this vault directory
+pub fn warn_missing_attachments(storage: &FileStorage, vault: &CredentialVault) {
+    let missing = storage.attachments().missing(vault);
+    if missing.is_empty() {
+        return;
+    }
+    for (credential, attachment) in &missing {
+        println!(
+            "⚠️ Attachment '{}' of {} has no file here yet",
+            attachment.name, credential.service
+        );
+    }
+    println!(
+        "💡 WebDAV and S3 sync only carry the vault file; copy the blobs into {} or sync with 'pwdmgr git'",
+        storage.vault_dir().join(ATTACHMENTS_DIR).display()
+    );
+}
+
+/// Common utility: Hide secret values in output
+pub fn mask_secret(value: Option<&str>) -> &'static str {
+    match value {
+        Some(v) if !v.is_empty() => "••••••••",
+        _ => "(none)",
+    }
+}
+
+/// Common utility: Colour a strength rating red, yellow or green by its score
+pub fn styled_strength(strength: &Strength) -> String {
+    let style = match strength.score {
+        0 | 1 => Style::new().red(),
+        2 => Style::new().yellow(),
+        _ => Style::new().green(),
+    };
+    style.apply_to(strength).to_string()
+}
+
+/// Common utility: Open the breach hash file given with `--hibp-file`, or else
+/// the one set in the config, if any
+pub fn open_hibp_file(storage: &FileStorage, path: Option<PathBuf>) -> Result<Option<HashFile>> {
+    let path = match path {
+        Some(path) => path,
+        None => match Config::load(storage.vault_dir())?.hibp_file {
+            Some(path) => path,
+            None => return Ok(None),
+        },
+    };
+    HashFile::open(&path).map(Some)
+}
+
+/// Common utility: Parse `--field`/`--secret-field` values of the form `name=value`.
+/// A bare `name` means the value should be prompted for.
+pub fn parse_field_arg(arg: &str) -> Result<(String, Option<String>)> {
+    let (name, value) = match arg.split_once('=') {
+        Some((name, value)) => (name.trim(), Some(value.to_string())),
+        None => (arg.trim(), None),
+    };
+
+    if name.is_empty() {
+        return Err(anyhow::anyhow!(
+            "❌ Invalid field '{}', expected name=value",
+            arg
+        ));
+    }
+
+    Ok((name.to_string(), value))
+}
+
+/// Common utility: Apply `--field` and `--secret-field` arguments to a credential,
+/// prompting (hidden) for secret values that were not given inline
+pub fn apply_field_args(
+    credential: &mut Credential,
+    fields: &[String],
+    secret_fields: &[String],
+) -> Result<()> {
+    for arg in fields {
+        let (name, value) = parse_field_arg(arg)?;
+        let value = match value {
+            Some(value) => value,
+            None => dialoguer::Input::new()
+                .with_prompt(format!("Value for '{}'", name))
+                .interact_text()?,
+        };
+        credential.set_field(name, value, false);
+    }
+
+    for arg in secret_fields {
+        let (name, value) = parse_field_arg(arg)?;
+        let value = match value {
+            Some(value) => value,
+            None => get_master_password(&format!("Enter secret value for '{}':", name))?,
+        };
+        credential.set_field(name, value, true);
+    }
+
+    Ok(())
+}
+
+/// Common utility: Print long output through $PAGER (default `less -FRX`)
+/// when it would not fit on the terminal; set PAGER="" to disable
+pub fn print_paged(output: &str) -> Result<()> {
+    let term = console::Term::stdout();
+    let fits = match term.size_checked() {
+        Some((rows, _)) => output.lines().count() < rows as usize,
+        None => true,
+    };
+    if fits || !std::io::stdout().is_terminal() {
+        print!("{}", output);
+        return Ok(());
+    }
+
+    let pager = std::env::var("PAGER").unwrap_or_else(|_| "less -FRX".to_string());
+    let mut words = pager.split_whitespace();
+    let Some(program) = words.next() else {
+        print!("{}", output);
+        return Ok(());
+    };
+
+    match Command::new(program)
+        .args(words)
+        .stdin(Stdio::piped())
+        .spawn()
+    {
+        Ok(mut child) => {
+            if let Some(mut stdin) = child.stdin.take() {
+                // The pager closing early (e.g. `q` in less) is not an error
+                let _ = stdin.write_all(output.as_bytes());
+            }
+            child.wait()?;
+        }
+        Err(_) => print!("{}", output),
+    }
+
+    Ok(())
+}
+
+/// Where a secret was copied to and when it will be cleared again
+pub struct Copied {
+    pub backend: Backend,
+    /// Seconds until the clipboard is cleared, `None` when clearing is turned off
+    pub clear_after: Option<u64>,
+}
+
+/// Common utility: Copy a secret and schedule the clipboard to be cleared.
+/// `clear_after` and `backend` override the config defaults.
+pub fn copy_secret(
+    storage: &FileStorage,
+    secret: &str,
+    clear_after: Option<u64>,
+    backend: Option<BackendChoice>,
+) -> Result<Copied> {
+    // A broken config should not stop the copy; fall back to the defaults
+    let config = Config::load(storage.vault_dir()).unwrap_or_default();
+    let backend = Backend::resolve(
+        backend.or(config.clipboard_backend).unwrap_or_default(),
+        config.clipboard_command.as_deref(),
+        config.clipboard_paste_command.as_deref(),
+    )?;
+    backend.copy(secret)?;
+
+    let seconds = clear_after
+        .or(config.clipboard_clear_after)
+        .unwrap_or(DEFAULT_CLEAR_AFTER);
+    if seconds == 0 {
+        return Ok(Copied {
+            backend,
+            clear_after: None,
+        });
+    }
+
+    clipboard::schedule_clear(&backend, secret, seconds)?;
+    Ok(Copied {
+        backend,
+        clear_after: Some(seconds),
+    })
+}
@@ -0,0 +1,81 @@
+use crate::commands::resolve::resolve_credential;
+use crate::commands::{ensure_vault_exists, get_master_password, get_storage};
+use crate::models::folder;
+use crate::utils::Utc;
+use anyhow::Result;
+
+/// Move a credential, or with `--folder` a whole folder, to another folder
+pub fn run(query: String, destination: String, whole_folder: bool) -> Result<()> {
+    let destination = folder::normalize(&destination)?;
+    let shown_destination = destination.as_deref().unwrap_or("/");
+
+    let storage = get_storage()?;
+    ensure_vault_exists(&storage)?;
+
+    let master_password = get_master_password("🔐 Enter master password to unlock vault:")?;
+    let mut vault = storage.load_vault(&master_password)?;
+    let now = Utc::now();
+
+    if whole_folder {
+        let Some(source) = folder::normalize(&query)? else {
+            return Err(anyhow::anyhow!(
+                "❌ Cannot move the vault root, move credentials one by one instead"
+            ));
+        };
+        if folder::contains(Some(&source), destination.as_deref(), true) {
+            return Err(anyhow::anyhow!("❌ Cannot move '{}' into itself", source));
+        }
+
+        let mut moved = 0;
+        for credential in vault.credentials.iter_mut() {
+            if let Some(new_folder) = folder::rebase(
+                credential.folder.as_deref(),
+                &source,
+                destination.as_deref(),
+            ) {
+                credential.folder = new_folder;
+                credential.updated_at = now;
+                moved += 1;
+            }
+        }
+
+        if moved == 0 {
+            println!("📭 No credentials found in folder '{}'.", source);
+            return Ok(());
+        }
+
+        vault.updated_at = now;
+        storage.save_vault(&vault, &master_password)?;
+        println!(
+            "✅ Moved folder '{}' ({} credentials) to '{}'",
+            source, moved, shown_destination
+        );
+        return Ok(());
+    }
+
+    let Some(id) = resolve_credential(&vault, &query)? else {
+        println!("❌ No credential found matching '{}'.", query);
+        return Ok(());
+    };
+    let credential = vault
+        .find_credential_mut(&id)
+        .ok_or_else(|| anyhow::anyhow!("Credential disappeared while moving"))?;
+
+    if credential.folder == destination {
+        println!(
+            "💡 {} is already in '{}'",
+            credential.service, shown_destination
+        );
+        return Ok(());
+    }
+
+    credential.folder = destination.clone();
+    credential.updated_at = now;
+    let service = credential.service.clone();
+    vault.updated_at = now;
+
+    storage.save_vault(&vault, &master_password)?;
+    println!("✅ Moved {} to '{}'", service, shown_destination);
+
+    Ok(())
+}
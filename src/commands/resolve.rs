@@ -0,0 +1,192 @@
+// Shared lookup of a single credential from a user query
+use crate::commands::is_interactive;
+use crate::models::credential::{Credential, CredentialVault};
+use anyhow::Result;
+use dialoguer::Select;
+use uuid::Uuid;
+
+/// How well a credential matched, best first
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum MatchKind {
+    Exact,
+    Prefix,
+    Substring,
+}
+
+/// Which field matched; a service match beats a username or URL match
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+enum MatchField {
+    Service,
+    Username,
+    Url,
+}
+
+/// Every credential matching `query`, best match first
+pub fn rank_matches<'a>(
+    vault: &'a CredentialVault,
+    query: &str,
+) -> Vec<(MatchKind, &'a Credential)> {
+    if let Ok(uuid) = Uuid::parse_str(query) {
+        return vault
+            .find_credential(&uuid)
+            .map(|c| vec![(MatchKind::Exact, c)])
+            .unwrap_or_default();
+    }
+
+    let query = query.trim().to_lowercase();
+    if query.is_empty() {
+        return Vec::new();
+    }
+
+    let mut matches: Vec<_> = vault
+        .credentials
+        .iter()
+        .filter_map(|c| best_match(c, &query).map(|rank| (rank, c)))
+        .collect();
+    // Stable sort keeps vault order among equally good matches
+    matches.sort_by_key(|(rank, _)| *rank);
+
+    matches
+        .into_iter()
+        .map(|((kind, _), credential)| (kind, credential))
+        .collect()
+}
+
+fn best_match(credential: &Credential, query: &str) -> Option<(MatchKind, MatchField)> {
+    [
+        (MatchField::Service, Some(credential.service.as_str())),
+        (MatchField::Username, credential.username.as_deref()),
+        (MatchField::Url, credential.url.as_deref()),
+    ]
+    .into_iter()
+    .filter_map(|(field, value)| {
+        let value = value?.to_lowercase();
+        let kind = if value == query {
+            MatchKind::Exact
+        } else if value.starts_with(query) {
+            MatchKind::Prefix
+        } else if value.contains(query) {
+            MatchKind::Substring
+        } else {
+            return None;
+        };
+        Some((kind, field))
+    })
+    .min()
+}
+
+/// Resolve `query` to one credential. `Ok(None)` means nothing matched.
+///
+/// A single best match wins outright. Several equally good matches open a
+/// picker on a terminal and are an error otherwise, so scripts never act on
+/// the wrong entry.
+pub fn resolve_credential(vault: &CredentialVault, query: &str) -> Result<Option<Uuid>> {
+    resolve(vault, query, is_interactive())
+}
+
+fn resolve(vault: &CredentialVault, query: &str, interactive: bool) -> Result<Option<Uuid>> {
+    let matches = rank_matches(vault, query);
+    let Some((best, _)) = matches.first() else {
+        return Ok(None);
+    };
+
+    let tied = matches.iter().filter(|(kind, _)| kind == best).count();
+    if tied == 1 {
+        return Ok(Some(matches[0].1.id));
+    }
+
+    if !interactive {
+        let candidates: Vec<String> = matches
+            .iter()
+            .take(tied)
+            .map(|(_, c)| format!("  {}", describe(c)))
+            .collect();
+        return Err(anyhow::anyhow!(
+            "❌ '{}' matches {} credentials:\n{}\n💡 Use a more specific query or the credential ID",
+            query,
+            tied,
+            candidates.join("\n")
+        ));
+    }
+
+    let items: Vec<String> = matches.iter().map(|(_, c)| describe(c)).collect();
+    let selection = Select::new()
+        .with_prompt(format!("'{}' matches several credentials, pick one", query))
+        .items(&items)
+        .default(0)
+        .interact_opt()?;
+
+    match selection {
+        Some(index) => Ok(Some(matches[index].1.id)),
+        None => Err(anyhow::anyhow!("❌ No credential selected")),
+    }
+}
+
+/// One-line summary used in the picker and ambiguity errors
+fn describe(credential: &Credential) -> String {
+    let mut line = credential.service.clone();
+    if let Some(username) = &credential.username {
+        line.push_str(&format!(" ({})", username));
+    }
+    if let Some(folder) = &credential.folder {
+        line.push_str(&format!(" [{}]", folder));
+    }
+    line.push_str(&format!(" {}", credential.id));
+    line
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn vault(services: &[(&str, Option<&str>)]) -> CredentialVault {
+        let mut vault = CredentialVault::new();
+        for (service, username) in services {
+            let mut cred = Credential::new(service.to_string(), "pw".into());
+            cred.username = username.map(str::to_string);
+            vault.add_credentail(cred);
+        }
+        vault
+    }
+
+    #[test]
+    fn test_ranking_prefers_exact_then_prefix_then_substring() {
+        let vault = vault(&[
+            ("My GitLab", None),
+            ("GitHub Enterprise", None),
+            ("GitHub", None),
+        ]);
+
+        let ranked: Vec<_> = rank_matches(&vault, "github")
+            .into_iter()
+            .map(|(kind, c)| (kind, c.service.as_str()))
+            .collect();
+        assert_eq!(
+            ranked,
+            vec![
+                (MatchKind::Exact, "GitHub"),
+                (MatchKind::Prefix, "GitHub Enterprise"),
+            ]
+        );
+
+        // A unique exact match resolves even when other entries match loosely
+        let id = resolve(&vault, "GitHub", false).unwrap().unwrap();
+        assert_eq!(vault.find_credential(&id).unwrap().service, "GitHub");
+    }
+
+    #[test]
+    fn test_service_match_beats_username_match() {
+        let vault = vault(&[("Mail", Some("github-bot")), ("github", None)]);
+        let ranked = rank_matches(&vault, "git");
+        assert_eq!(ranked[0].1.service, "github");
+        assert_eq!(ranked[1].1.service, "Mail");
+    }
+
+    #[test]
+    fn test_ambiguous_query_errors_without_a_terminal() {
+        let vault = vault(&[("GitHub", None), ("GitLab", None)]);
+        let err = resolve(&vault, "git", false).unwrap_err();
+        assert!(err.to_string().contains("matches 2 credentials"));
+        assert!(resolve(&vault, "nothing", false).unwrap().is_none());
+    }
+}
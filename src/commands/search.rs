@@ -1,26 +1,105 @@
-use crate::commands::{ensure_vault_exists, get_master_password, get_storage};
+use crate::commands::{ensure_vault_exists, get_storage, unlock_password};
+use crate::models::query::Query;
+use crate::models::search::{self, SearchField, SearchHit};
+use console::Style;
+
+pub fn run(term: String, exact: bool) -> anyhow::Result<()> {
+    // Parse before unlocking so typos in the query fail fast
+    let query = Query::parse(&term)?;
 
-pub fn run(term: String) -> anyhow::Result<()> {
     let storage = get_storage()?;
     ensure_vault_exists(&storage)?;
 
     //Prompt user for master password
-    let master_password = get_master_password("🔐 Enter master password to unlock vault:")?;
+    let master_password = unlock_password()?;
 
     // Load vault
     let vault = storage.load_vault(&master_password)?;
 
-    // Search credentials using matches_search method
-    let results: Vec<_> = vault.search_credentials(&term);
+    let results = search::find(&vault, &query, exact);
 
     if results.is_empty() {
         println!("🔍 No credentials found matching '{}'.", term);
+        if exact {
+            println!("💡 Drop --exact to allow fuzzy matches");
+        }
     } else {
         println!("🔍 Found {} matching credential(s):", results.len());
-        for credential in results {
-            println!("{}", credential.display_safe());
+        for (index, hit) in results.iter().enumerate() {
+            print_hit(index + 1, hit);
             println!("---");
         }
     }
     Ok(())
 }
+
+fn print_hit(number: usize, hit: &SearchHit) {
+    let credential = hit.credential;
+    let title = Style::new().bold();
+    let dim = Style::new().dim();
+    let plain = Style::new();
+
+    println!(
+        "{}. {}",
+        number,
+        highlight(
+            &credential.service,
+            hit.positions(SearchField::Service),
+            &title
+        )
+    );
+    if let Some(username) = &credential.username {
+        println!(
+            "   Username: {}",
+            highlight(username, hit.positions(SearchField::Username), &plain)
+        );
+    }
+    if let Some(url) = &credential.url {
+        println!(
+            "   URL: {}",
+            highlight(url, hit.positions(SearchField::Url), &plain)
+        );
+    }
+    if let Some(folder) = &credential.folder {
+        println!(
+            "   Folder: {}",
+            highlight(folder, hit.positions(SearchField::Folder), &plain)
+        );
+    }
+    if !credential.tags.is_empty() {
+        let tags: Vec<String> = credential
+            .tags
+            .iter()
+            .enumerate()
+            .map(|(i, tag)| highlight(tag, hit.positions(SearchField::Tag(i)), &plain))
+            .collect();
+        println!("   Tags: {}", tags.join(", "));
+    }
+    println!("   {}", dim.apply_to(format!("ID {}", credential.id)));
+}
+
+/// Colour the matched characters of `text`, drawing the rest in `base`
+fn highlight(text: &str, positions: &[usize], base: &Style) -> String {
+    let matched = Style::new().yellow().bold().underlined();
+    let mut output = String::new();
+    let mut run = String::new();
+    let mut run_matched = false;
+
+    // Style runs of matched/unmatched characters rather than every character
+    for (i, c) in text.chars().enumerate() {
+        let is_match = positions.contains(&i);
+        if is_match != run_matched && !run.is_empty() {
+            let style = if run_matched { &matched } else { base };
+            output.push_str(&style.apply_to(&run).to_string());
+            run.clear();
+        }
+        run_matched = is_match;
+        run.push(c);
+    }
+    if !run.is_empty() {
+        let style = if run_matched { &matched } else { base };
+        output.push_str(&style.apply_to(&run).to_string());
+    }
+
+    output
+}
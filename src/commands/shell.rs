@@ -0,0 +1,432 @@
+use crate::Cli;
+use crate::commands::{
+    ensure_vault_exists, get_master_password, get_storage, set_session_password,
+};
+use crate::storage::file::FileStorage;
+use anyhow::Result;
+use clap::Parser;
+use rustyline::completion::{Completer, Pair};
+use rustyline::error::ReadlineError;
+use rustyline::history::DefaultHistory;
+use rustyline::{Context, Editor, Helper, Highlighter, Hinter, Validator};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::{Duration, Instant};
+
+/// Subcommands that can be run at the shell prompt
+const SHELL_COMMANDS: [&str; 5] = ["get", "search", "add", "delete", "list"];
+
+/// Commands handled by the shell itself
+const BUILTINS: [&str; 4] = ["help", "lock", "exit", "quit"];
+
+/// Commands whose argument is an existing credential, completed from the vault
+const QUERY_COMMANDS: [&str; 3] = ["get", "delete", "search"];
+
+/// Wrong master passwords allowed when unlocking a locked session
+const UNLOCK_ATTEMPTS: usize = 3;
+
+/// Tab completion of command and credential names
+#[derive(Helper, Hinter, Highlighter, Validator)]
+struct ShellHelper {
+    names: Arc<Mutex<Vec<String>>>,
+}
+
+impl Completer for ShellHelper {
+    type Candidate = Pair;
+
+    fn complete(
+        &self,
+        line: &str,
+        pos: usize,
+        _ctx: &Context<'_>,
+    ) -> rustyline::Result<(usize, Vec<Pair>)> {
+        let names = self.names.lock().unwrap();
+        let (start, candidates) = complete(&names, &line[..pos]);
+        let pairs = candidates
+            .into_iter()
+            .map(|(display, replacement)| Pair {
+                display,
+                replacement,
+            })
+            .collect();
+        Ok((start, pairs))
+    }
+}
+
+/// Wipes the session password however the shell exits
+struct SessionGuard;
+
+impl Drop for SessionGuard {
+    fn drop(&mut self) {
+        set_session_password(None);
+    }
+}
+
+pub fn run(timeout_minutes: u64) -> Result<()> {
+    let storage = get_storage()?;
+    ensure_vault_exists(&storage)?;
+
+    let master_password = get_master_password("🔐 Enter master password to unlock vault:")?;
+    let names = Arc::new(Mutex::new(service_names(&storage, &master_password)?));
+    set_session_password(Some(master_password));
+    let _guard = SessionGuard;
+
+    println!("🔓 Vault unlocked. Type 'help' for commands, 'exit' to leave.");
+
+    let locked = Arc::new(AtomicBool::new(false));
+    let idle_since = Arc::new(Mutex::new(None));
+    if timeout_minutes > 0 {
+        println!(
+            "🔒 The session locks after {} min without input.",
+            timeout_minutes
+        );
+        spawn_watchdog(
+            Duration::from_secs(timeout_minutes * 60),
+            idle_since.clone(),
+            locked.clone(),
+            names.clone(),
+        );
+    }
+
+    let mut editor: Editor<ShellHelper, DefaultHistory> = Editor::new()?;
+    editor.set_helper(Some(ShellHelper {
+        names: names.clone(),
+    }));
+
+    loop {
+        *idle_since.lock().unwrap() = Some(Instant::now());
+        let line = editor.readline("pwdmgr> ");
+        *idle_since.lock().unwrap() = None;
+
+        let line = match line {
+            Ok(line) => line,
+            Err(ReadlineError::Interrupted) => continue,
+            Err(ReadlineError::Eof) => break,
+            Err(e) => return Err(e.into()),
+        };
+
+        if locked.load(Ordering::SeqCst) {
+            // Even the command history is forgotten once the session locks
+            let _ = editor.clear_history();
+            println!("🔒 Session locked.");
+            let Some(password) = unlock(&storage, &names) else {
+                println!("❌ Too many failed attempts.");
+                break;
+            };
+            set_session_password(Some(password));
+            locked.store(false, Ordering::SeqCst);
+        }
+
+        let words = match split_words(&line) {
+            Ok(words) => words,
+            Err(e) => {
+                println!("{}", e);
+                continue;
+            }
+        };
+        let Some(command) = words.first().cloned() else {
+            continue;
+        };
+        if should_record(&line, &words) {
+            let _ = editor.add_history_entry(line.as_str());
+        }
+
+        match command.as_str() {
+            "exit" | "quit" => break,
+            "help" => print_help(),
+            "lock" => {
+                set_session_password(None);
+                names.lock().unwrap().clear();
+                locked.store(true, Ordering::SeqCst);
+                println!("🔒 Locked. Enter any command to unlock again.");
+            }
+            name if SHELL_COMMANDS.contains(&name) => {
+                let args = std::iter::once("pwdmgr".to_string()).chain(words);
+                match Cli::try_parse_from(args) {
+                    Ok(cli) => {
+                        if let Err(e) = crate::run(cli.command) {
+                            println!("{}", e);
+                        }
+                        if matches!(name, "add" | "delete") {
+                            refresh_names(&storage, &names);
+                        }
+                    }
+                    // Usage errors and `--help` output
+                    Err(e) => {
+                        let _ = e.print();
+                    }
+                }
+            }
+            other => println!(
+                "❌ '{}' is not available in the shell. Type 'help' for commands.",
+                other
+            ),
+        }
+    }
+
+    println!("👋 Vault locked. Bye!");
+    Ok(())
+}
+
+fn print_help() {
+    println!("Commands:");
+    println!("  get <query> [--copy] [--reveal]   Show a credential");
+    println!("  search <term>                     Search credentials");
+    println!("  add <title> [options]             Add a credential");
+    println!("  delete <query> [--force]          Delete a credential");
+    println!("  list [options]                    List credentials");
+    println!("  lock                              Lock until the next command");
+    println!("  exit                              Leave the shell");
+    println!();
+    println!("💡 Add --help to a command for its options. Tab completes credential names.");
+    println!("💡 Lines starting with a space are not kept in the history.");
+}
+
+/// Lock after `timeout` at the prompt: wipe the password and the completion names
+fn spawn_watchdog(
+    timeout: Duration,
+    idle_since: Arc<Mutex<Option<Instant>>>,
+    locked: Arc<AtomicBool>,
+    names: Arc<Mutex<Vec<String>>>,
+) {
+    thread::spawn(move || {
+        loop {
+            thread::sleep(Duration::from_secs(1));
+            let expired = idle_since
+                .lock()
+                .unwrap()
+                .is_some_and(|at| at.elapsed() >= timeout);
+            if expired && !locked.swap(true, Ordering::SeqCst) {
+                set_session_password(None);
+                names.lock().unwrap().clear();
+            }
+        }
+    });
+}
+
+/// Ask for the master password again, refilling the completion names on success
+fn unlock(storage: &FileStorage, names: &Mutex<Vec<String>>) -> Option<String> {
+    for _ in 0..UNLOCK_ATTEMPTS {
+        let password = get_master_password("🔐 Enter master password to unlock vault:").ok()?;
+        match service_names(storage, &password) {
+            Ok(fresh) => {
+                *names.lock().unwrap() = fresh;
+                return Some(password);
+            }
+            Err(e) => println!("{}", e),
+        }
+    }
+    None
+}
+
+/// Decrypt the vault quietly and collect the credential names for completion
+fn service_names(storage: &FileStorage, master_password: &str) -> Result<Vec<String>> {
+    let vault = FileStorage::decrypt_vault_file(&storage.load_vault_file()?, master_password)?;
+    let mut names: Vec<String> = vault
+        .credentials
+        .iter()
+        .map(|c| c.service.clone())
+        .collect();
+    names.sort_by_key(|name| name.to_lowercase());
+    names.dedup();
+    Ok(names)
+}
+
+fn refresh_names(storage: &FileStorage, names: &Mutex<Vec<String>>) {
+    if let Ok(password) = crate::commands::unlock_password()
+        && let Ok(fresh) = service_names(storage, &password)
+    {
+        *names.lock().unwrap() = fresh;
+    }
+}
+
+/// Lines starting with a space, or carrying a secret field value, stay out of the history
+fn should_record(line: &str, words: &[String]) -> bool {
+    if line.starts_with(' ') {
+        return false;
+    }
+    !words.iter().enumerate().any(|(i, word)| {
+        word.starts_with("--secret-field=")
+            || (word == "--secret-field" && words.get(i + 1).is_some_and(|v| v.contains('=')))
+    })
+}
+
+/// Split a line into words the way a shell would: quotes group, backslash escapes
+fn split_words(line: &str) -> Result<Vec<String>> {
+    let mut words = Vec::new();
+    let mut current: Option<String> = None;
+    let mut chars = line.chars();
+
+    while let Some(c) = chars.next() {
+        match c {
+            c if c.is_whitespace() => {
+                if let Some(word) = current.take() {
+                    words.push(word);
+                }
+            }
+            '\'' => {
+                let word = current.get_or_insert_with(String::new);
+                loop {
+                    match chars.next() {
+                        Some('\'') => break,
+                        Some(c) => word.push(c),
+                        None => return Err(anyhow::anyhow!("❌ Unclosed quote")),
+                    }
+                }
+            }
+            '"' => {
+                let word = current.get_or_insert_with(String::new);
+                loop {
+                    match chars.next() {
+                        Some('"') => break,
+                        Some('\\') => match chars.next() {
+                            Some(c @ ('"' | '\\')) => word.push(c),
+                            Some(c) => {
+                                word.push('\\');
+                                word.push(c);
+                            }
+                            None => return Err(anyhow::anyhow!("❌ Unclosed quote")),
+                        },
+                        Some(c) => word.push(c),
+                        None => return Err(anyhow::anyhow!("❌ Unclosed quote")),
+                    }
+                }
+            }
+            '\\' => {
+                let word = current.get_or_insert_with(String::new);
+                if let Some(c) = chars.next() {
+                    word.push(c);
+                }
+            }
+            c => current.get_or_insert_with(String::new).push(c),
+        }
+    }
+
+    if let Some(word) = current {
+        words.push(word);
+    }
+    Ok(words)
+}
+
+/// Completion candidates `(display, replacement)` for the word being typed at the
+/// end of `text`, and the byte offset where that word starts
+fn complete(names: &[String], text: &str) -> (usize, Vec<(String, String)>) {
+    // Find where the last word starts, treating quoted spaces as part of it
+    let mut start = 0;
+    let mut words_before = 0;
+    let mut quote = None;
+    let mut in_word = false;
+    for (i, c) in text.char_indices() {
+        match quote {
+            Some(q) if c == q => quote = None,
+            Some(_) => {}
+            None if c.is_whitespace() => {
+                if in_word {
+                    words_before += 1;
+                    in_word = false;
+                }
+            }
+            None => {
+                if !in_word {
+                    start = i;
+                    in_word = true;
+                }
+                if c == '"' || c == '\'' {
+                    quote = Some(c);
+                }
+            }
+        }
+    }
+    if !in_word {
+        start = text.len();
+    }
+    let word = &text[start..];
+    let prefix = word.trim_start_matches(['"', '\'']).to_lowercase();
+
+    let candidates = if words_before == 0 {
+        SHELL_COMMANDS
+            .iter()
+            .chain(BUILTINS.iter())
+            .filter(|command| command.starts_with(&prefix))
+            .map(|command| (command.to_string(), format!("{} ", command)))
+            .collect()
+    } else {
+        let command = text.split_whitespace().next().unwrap_or_default();
+        if !QUERY_COMMANDS.contains(&command) || prefix.starts_with('-') {
+            Vec::new()
+        } else {
+            names
+                .iter()
+                .filter(|name| name.to_lowercase().starts_with(&prefix))
+                .map(|name| (name.clone(), quote_word(name)))
+                .collect()
+        }
+    };
+
+    (start, candidates)
+}
+
+/// Quote a credential name so it survives `split_words` as one word
+fn quote_word(name: &str) -> String {
+    if name.is_empty() || name.contains(|c: char| c.is_whitespace() || "\"'\\".contains(c)) {
+        format!("\"{}\"", name.replace('\\', "\\\\").replace('"', "\\\""))
+    } else {
+        name.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_split_words() {
+        assert_eq!(
+            split_words(r#"get "Prod console" --copy"#).unwrap(),
+            ["get", "Prod console", "--copy"]
+        );
+        assert_eq!(
+            split_words("get 'oops").unwrap_err().to_string(),
+            "❌ Unclosed quote"
+        );
+        assert_eq!(
+            split_words(r#"add a\ b 'x "y"' "c\"d""#).unwrap(),
+            ["add", "a b", "x \"y\"", "c\"d"]
+        );
+        for name in ["Prod console", "Bob's \"Bank\"", "C:\\x"] {
+            let line = format!("get {}", quote_word(name));
+            assert_eq!(split_words(&line).unwrap()[1], name);
+        }
+    }
+
+    #[test]
+    fn test_history_skips_secrets() {
+        let record = |line: &str| should_record(line, &split_words(line).unwrap());
+        assert!(record("get gitlab --copy"));
+        assert!(record("add AWS --secret-field api-key"));
+        assert!(!record("add AWS --secret-field api-key=hunter2"));
+        assert!(!record("add AWS --secret-field=pin=1234"));
+        assert!(!record(" get gitlab"));
+    }
+
+    #[test]
+    fn test_complete() {
+        let names = vec!["GitHub".to_string(), "Prod console".to_string()];
+        let replacements = |text: &str| {
+            let (start, candidates) = complete(&names, text);
+            let replaced: Vec<_> = candidates.into_iter().map(|(_, r)| r).collect();
+            (start, replaced)
+        };
+
+        assert_eq!(replacements("se"), (0, vec!["search ".to_string()]));
+        assert_eq!(replacements("get git"), (4, vec!["GitHub".to_string()]));
+        assert_eq!(
+            replacements("get \"prod c"),
+            (4, vec!["\"Prod console\"".to_string()])
+        );
+        // `add` takes a new title, so nothing to complete
+        assert!(replacements("add Gi").1.is_empty());
+        assert!(replacements("get --c").1.is_empty());
+    }
+}
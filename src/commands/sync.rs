@@ -0,0 +1,267 @@
+use crate::commands::{
+    ensure_vault_exists, get_master_password, get_storage, unlock_password,
+    warn_missing_attachments,
+};
+use crate::models::merge::{last_writer_wins, merge_vaults};
+use crate::storage::config::{Config, ProfileState, SyncProfile, SyncState};
+use crate::storage::file::{FileStorage, VaultFile};
+use crate::storage::remote::{Fetch, Precondition, RemoteStore, Upload};
+use anyhow::Result;
+use dialoguer::{Input, Select};
+use sha2::{Digest, Sha256};
+use std::fs;
+
+/// How many times to retry when the remote changes underneath us
+const MAX_ATTEMPTS: usize = 3;
+
+/// Configure a remote for a sync profile
+pub fn setup(profile: String) -> Result<()> {
+    let storage = get_storage()?;
+    ensure_vault_exists(&storage)?;
+
+    let mut config = Config::load(storage.vault_dir())?;
+
+    println!("🔧 Configuring sync profile: {}", profile);
+
+    let providers = ["WebDAV", "S3-compatible (AWS, MinIO, ...)"];
+    let provider = Select::new()
+        .with_prompt("Remote type")
+        .items(&providers)
+        .default(0)
+        .interact()?;
+
+    let (sync_profile, secret) = if provider == 0 {
+        let url: String = Input::new()
+            .with_prompt("Vault file URL (e.g. https://dav.example.com/pwdmgr/vault.vault)")
+            .interact_text()?;
+        let username: String = Input::new()
+            .with_prompt("Username (optional, press Enter to skip)")
+            .allow_empty(true)
+            .interact_text()?;
+        let password = if username.is_empty() {
+            None
+        } else {
+            Some(get_master_password("WebDAV password:")?)
+        };
+
+        let profile = SyncProfile::Webdav {
+            url,
+            username: if username.is_empty() {
+                None
+            } else {
+                Some(username)
+            },
+            password: None,
+        };
+        (profile, password)
+    } else {
+        let endpoint: String = Input::new()
+            .with_prompt("Endpoint (e.g. https://s3.amazonaws.com or http://localhost:9000)")
+            .interact_text()?;
+        let region: String = Input::new()
+            .with_prompt("Region")
+            .default("us-east-1".to_string())
+            .interact_text()?;
+        let bucket: String = Input::new().with_prompt("Bucket").interact_text()?;
+        let key: String = Input::new()
+            .with_prompt("Object key")
+            .default("vault.vault".to_string())
+            .interact_text()?;
+        let access_key_id: String = Input::new().with_prompt("Access key ID").interact_text()?;
+        let secret_access_key = get_master_password("Secret access key:")?;
+
+        let profile = SyncProfile::S3 {
+            endpoint,
+            region,
+            bucket,
+            key,
+            access_key_id,
+            secret_access_key: None,
+        };
+        (profile, Some(secret_access_key))
+    };
+
+    // The secret goes into the encrypted vault, the rest into config.json
+    let master_password = unlock_password()?;
+    let mut vault = storage.load_vault(&master_password)?;
+    match secret {
+        Some(secret) => vault.sync_secrets.insert(profile.clone(), secret),
+        None => vault.sync_secrets.remove(&profile),
+    };
+    storage.save_vault(&vault, &master_password)?;
+
+    config.sync_profiles.insert(profile.clone(), sync_profile);
+    config.save(storage.vault_dir())?;
+
+    // A new remote means previous sync bookkeeping no longer applies
+    let mut state = SyncState::load(storage.vault_dir())?;
+    state.profiles.remove(&profile);
+    state.save(storage.vault_dir())?;
+
+    println!("✅ Sync profile '{}' saved!", profile);
+    println!("📍 Config: {}", Config::path(storage.vault_dir()).display());
+    println!("💡 Sync now with: pwdmgr sync --profile {}", profile);
+
+    Ok(())
+}
+
+/// Two-way sync of the encrypted vault blob with the profile's remote
+pub fn run(profile: String) -> Result<()> {
+    let storage = get_storage()?;
+    ensure_vault_exists(&storage)?;
+
+    let mut config = Config::load(storage.vault_dir())?;
+    let Some(sync_profile) = config.sync_profiles.get_mut(&profile) else {
+        println!("❌ No sync profile named '{}'.", profile);
+        println!(
+            "💡 Configure one with: pwdmgr sync setup --profile {}",
+            profile
+        );
+        return Err(anyhow::anyhow!("Sync profile not found"));
+    };
+
+    let legacy_secret = sync_profile.take_legacy_secret();
+    let sync_profile = sync_profile.clone();
+
+    // Remote credentials live in the encrypted vault, so unlock it first
+    let mut master_password: Option<String> = None;
+    let mut secret = None;
+    if sync_profile.needs_secret() || legacy_secret.is_some() {
+        let password = unlock_password()?;
+        let mut vault = storage.load_vault(&password)?;
+        if let Some(legacy_secret) = legacy_secret {
+            vault.sync_secrets.insert(profile.clone(), legacy_secret);
+            storage.save_vault(&vault, &password)?;
+            config.save(storage.vault_dir())?;
+            println!(
+                "🔐 Moved the secret of sync profile '{}' from config.json into the vault.",
+                profile
+            );
+        }
+
+        secret = vault.sync_secrets.get(&profile).cloned();
+        if secret.is_none() && sync_profile.needs_secret() {
+            println!(
+                "💡 Store it again with: pwdmgr sync setup --profile {}",
+                profile
+            );
+            return Err(anyhow::anyhow!(
+                "❌ The vault has no secret for sync profile '{}'",
+                profile
+            ));
+        }
+        master_password = Some(password);
+    }
+
+    let remote = RemoteStore::from_profile(&sync_profile, secret.as_deref())?;
+    let mut state = SyncState::load(storage.vault_dir())?;
+    let mut last = state.profiles.get(&profile).cloned().unwrap_or_default();
+
+    println!("🔄 Syncing vault with profile '{}'...", profile);
+
+    for _ in 0..MAX_ATTEMPTS {
+        let local = fs::read(storage.get_vault_path())?;
+        let local_changed = last.local_hash.as_deref() != Some(&hash(&local));
+
+        let upload_precondition = match remote.fetch(last.etag.as_deref())? {
+            Fetch::NotFound => Precondition::Create,
+            Fetch::NotModified if !local_changed => {
+                println!("✅ Vault is already in sync.");
+                return Ok(());
+            }
+            Fetch::NotModified => Precondition::Match(last.etag.clone().unwrap_or_default()),
+            Fetch::Found { data, etag } => {
+                let remote_file: VaultFile = serde_json::from_slice(&data)
+                    .map_err(|e| anyhow::anyhow!("Remote file is not a vault: {}", e))?;
+
+                if data == local {
+                    record(&storage, &mut state, &profile, etag, &local)?;
+                    println!("✅ Vault is already in sync.");
+                    return Ok(());
+                }
+
+                if !local_changed {
+                    // Only the remote moved on, take it as-is
+                    storage.replace_vault_file(&remote_file, "Sync vault from remote")?;
+                    let local = fs::read(storage.get_vault_path())?;
+                    record(&storage, &mut state, &profile, etag, &local)?;
+                    println!("⬇️ Vault updated from remote.");
+                    // Checking needs the vault unlocked; don't prompt just for this
+                    if let Some(password) = &master_password {
+                        warn_missing_attachments(&storage, &storage.load_vault(password)?);
+                    }
+                    return Ok(());
+                }
+
+                // Both sides changed: merge credentials then upload the result
+                println!("🔀 Local and remote vaults have both changed, merging credentials...");
+                let password = match &master_password {
+                    Some(password) => password.clone(),
+                    None => get_master_password("🔐 Enter master password to merge vaults:")?,
+                };
+                let ours = storage.load_vault(&password)?;
+                let theirs =
+                    FileStorage::decrypt_vault_file(&remote_file, &password).map_err(|_| {
+                        anyhow::anyhow!(
+                            "❌ Could not decrypt remote vault with this master password"
+                        )
+                    })?;
+
+                let (merged, report) = merge_vaults(None, &ours, &theirs, last_writer_wins);
+                storage.save_vault(&merged, &password)?;
+                println!(
+                    "📊 {} added, {} updated from remote",
+                    report.added, report.updated
+                );
+                warn_missing_attachments(&storage, &merged);
+                master_password = Some(password);
+
+                match etag {
+                    Some(etag) => Precondition::Match(etag),
+                    None => return Err(anyhow::anyhow!("Remote did not return an ETag")),
+                }
+            }
+        };
+
+        let local = fs::read(storage.get_vault_path())?;
+        match remote.upload(&local, &upload_precondition)? {
+            Upload::Stored { etag } => {
+                record(&storage, &mut state, &profile, etag, &local)?;
+                println!("⬆️ Vault uploaded to remote.");
+                return Ok(());
+            }
+            Upload::Conflict => {
+                println!("⚠️ Remote changed during sync, retrying...");
+                last = state.profiles.get(&profile).cloned().unwrap_or_default();
+                // Force a full download next round
+                last.etag = None;
+            }
+        }
+    }
+
+    Err(anyhow::anyhow!(
+        "❌ Remote kept changing during sync, please try again"
+    ))
+}
+
+/// Remember what both sides looked like after a successful sync
+fn record(
+    storage: &FileStorage,
+    state: &mut SyncState,
+    profile: &str,
+    etag: Option<String>,
+    local: &[u8],
+) -> Result<()> {
+    state.profiles.insert(
+        profile.to_string(),
+        ProfileState {
+            etag,
+            local_hash: Some(hash(local)),
+        },
+    );
+    state.save(storage.vault_dir())
+}
+
+fn hash(data: &[u8]) -> String {
+    hex::encode(Sha256::digest(data))
+}
@@ -0,0 +1,182 @@
+use crate::commands::resolve::resolve_credential;
+use crate::commands::{ensure_vault_exists, get_master_password, get_storage};
+use crate::models::credential::{Credential, CredentialVault};
+use crate::storage::file::FileStorage;
+use crate::utils::Utc;
+use anyhow::Result;
+use std::collections::BTreeMap;
+use uuid::Uuid;
+
+/// Add tags to one credential, or every search match with `all`
+pub fn add(query: String, tags: Vec<String>, all: bool) -> Result<()> {
+    let (storage, master_password, mut vault) = unlock()?;
+    let ids = targets(&vault, &query, all)?;
+
+    let changed = update(&mut vault, &ids, |credential| {
+        tags.iter().filter(|tag| credential.add_tag(tag)).count() > 0
+    });
+
+    finish(&storage, &master_password, &vault, changed, "Tagged")
+}
+
+/// Remove tags from one credential, or every search match with `all`
+pub fn remove(query: String, tags: Vec<String>, all: bool) -> Result<()> {
+    let (storage, master_password, mut vault) = unlock()?;
+    let ids = targets(&vault, &query, all)?;
+
+    let changed = update(&mut vault, &ids, |credential| {
+        tags.iter().filter(|tag| credential.remove_tag(tag)).count() > 0
+    });
+
+    finish(&storage, &master_password, &vault, changed, "Untagged")
+}
+
+/// Rename a tag everywhere, or only on credentials matching `search`
+pub fn rename(old: String, new: String, search: Option<String>) -> Result<()> {
+    if new.trim().is_empty() {
+        return Err(anyhow::anyhow!("❌ New tag name can't be empty"));
+    }
+
+    let (storage, master_password, mut vault) = unlock()?;
+    let ids: Vec<Uuid> = vault
+        .credentials
+        .iter()
+        .filter(|c| c.has_tag(&old))
+        .filter(|c| search.as_ref().is_none_or(|term| c.matches_search(term)))
+        .map(|c| c.id)
+        .collect();
+
+    let changed = update(&mut vault, &ids, |credential| {
+        // Keep the tag where it was in the list unless the new name is already there
+        let index = credential
+            .tags
+            .iter()
+            .position(|t| t.eq_ignore_ascii_case(old.trim()));
+        match index {
+            Some(index) if credential.has_tag(&new) => {
+                credential.tags.remove(index);
+            }
+            Some(index) => credential.tags[index] = new.trim().to_string(),
+            None => return false,
+        }
+        true
+    });
+
+    finish(
+        &storage,
+        &master_password,
+        &vault,
+        changed,
+        "Renamed tag on",
+    )
+}
+
+/// Show tag usage counts, most used first
+pub fn list(search: Option<String>) -> Result<()> {
+    let (_storage, _master_password, vault) = unlock()?;
+
+    // Group case variants together under the first spelling seen
+    let mut counts: BTreeMap<String, (String, usize)> = BTreeMap::new();
+    for credential in vault
+        .credentials
+        .iter()
+        .filter(|c| search.as_ref().is_none_or(|term| c.matches_search(term)))
+    {
+        for tag in &credential.tags {
+            counts
+                .entry(tag.to_lowercase())
+                .or_insert_with(|| (tag.clone(), 0))
+                .1 += 1;
+        }
+    }
+
+    if counts.is_empty() {
+        println!("🏷️ No tags found.");
+        println!("💡 Tag a credential with: pwdmgr tag add <query> <tag>");
+        return Ok(());
+    }
+
+    let mut counts: Vec<_> = counts.into_values().collect();
+    counts.sort_by(|a, b| b.1.cmp(&a.1).then_with(|| a.0.cmp(&b.0)));
+
+    println!("🏷️ Tags ({}):\n", counts.len());
+    for (tag, count) in counts {
+        println!("  {} ({})", tag, count);
+    }
+
+    Ok(())
+}
+
+fn unlock() -> Result<(FileStorage, String, CredentialVault)> {
+    let storage = get_storage()?;
+    ensure_vault_exists(&storage)?;
+
+    let master_password = get_master_password("🔐 Enter master password to unlock vault:")?;
+    let vault = storage.load_vault(&master_password)?;
+
+    Ok((storage, master_password, vault))
+}
+
+/// The single matching credential, or every search match for bulk operations
+fn targets(vault: &CredentialVault, query: &str, all: bool) -> Result<Vec<Uuid>> {
+    let ids: Vec<Uuid> = if all {
+        vault
+            .search_credentials(query)
+            .iter()
+            .map(|c| c.id)
+            .collect()
+    } else {
+        resolve_credential(vault, query)?.into_iter().collect()
+    };
+
+    if ids.is_empty() {
+        return Err(anyhow::anyhow!(
+            "❌ No credential found matching '{}'.",
+            query
+        ));
+    }
+    Ok(ids)
+}
+
+/// Apply `change` to each credential, returning the names of those it modified
+fn update(
+    vault: &mut CredentialVault,
+    ids: &[Uuid],
+    mut change: impl FnMut(&mut Credential) -> bool,
+) -> Vec<String> {
+    let now = Utc::now();
+    let mut changed = Vec::new();
+
+    for credential in vault.credentials.iter_mut().filter(|c| ids.contains(&c.id)) {
+        if change(credential) {
+            credential.updated_at = now;
+            changed.push(credential.service.clone());
+        }
+    }
+
+    if !changed.is_empty() {
+        vault.updated_at = now;
+    }
+    changed
+}
+
+fn finish(
+    storage: &FileStorage,
+    master_password: &str,
+    vault: &CredentialVault,
+    changed: Vec<String>,
+    verb: &str,
+) -> Result<()> {
+    if changed.is_empty() {
+        println!("💡 Nothing to change.");
+        return Ok(());
+    }
+
+    storage.save_vault(vault, master_password)?;
+    for service in &changed {
+        println!("  🏷️ {}", service);
+    }
+    println!("✅ {} {} credential(s)", verb, changed.len());
+
+    Ok(())
+}
@@ -0,0 +1,13 @@
+use crate::commands::{ensure_vault_exists, get_master_password, get_storage};
+use anyhow::Result;
+
+pub fn run() -> Result<()> {
+    let storage = get_storage()?;
+    ensure_vault_exists(&storage)?;
+
+    // Unlock once; the UI keeps the vault open until it exits
+    let master_password = get_master_password("🔐 Enter master password to unlock vault:")?;
+    let vault = storage.load_vault(&master_password)?;
+
+    crate::tui::run(storage, vault, master_password)
+}
@@ -1,9 +1,20 @@
 use anyhow::Result;
-use clap::{Parser, Subcommand};
+use clap::{Args, Parser, Subcommand};
+use std::path::PathBuf;
+
+use commands::audit::{Check, DEFAULT_MAX_AGE};
+use commands::generate::GenerateArgs;
+use commands::get::GetArgs;
+use commands::list::{Column, ListArgs, SortKey};
+use models::item::ItemKind;
+use storage::import::ImportFormat;
+use utils::clipboard::BackendChoice;
+use utils::generator::{DEFAULT_LENGTH, DEFAULT_SYMBOLS, PassphraseOptions, Policy};
 
 mod commands;
 mod models;
 mod storage;
+mod tui;
 mod utils;
 
 #[derive(Parser)]
@@ -41,6 +52,83 @@ pub enum Commands {
         /// Genrate random password
         #[arg(short = 'g', long)]
         generate: bool,
+
+        /// Item type: login, note, card, identity, ssh-key, api-token
+        #[arg(long = "type", default_value = "login")]
+        kind: ItemKind,
+
+        /// Folder path, e.g. work/aws/prod
+        #[arg(short, long)]
+        folder: Option<String>,
+
+        /// Tag (repeatable)
+        #[arg(short, long = "tag", value_name = "TAG")]
+        tags: Vec<String>,
+
+        /// Custom field as name=value (repeatable)
+        #[arg(long = "field", value_name = "NAME=VALUE")]
+        fields: Vec<String>,
+
+        /// Concealed custom field as name=value, or just name to be prompted (repeatable)
+        #[arg(long = "secret-field", value_name = "NAME[=VALUE]")]
+        secret_fields: Vec<String>,
+
+        /// Check the password against a local Have I Been Pwned SHA-1 file
+        #[arg(long, value_name = "PATH")]
+        hibp_file: Option<PathBuf>,
+
+        /// Site password rules, e.g. "required: lower; required: digit; maxlength: 20"
+        #[arg(long, value_name = "RULES")]
+        password_rules: Option<String>,
+
+        #[command(flatten)]
+        passphrase: PassphraseFlags,
+    },
+
+    /// Edit an existing credential
+    Edit {
+        /// Search term (title, username, or ID)
+        query: String,
+
+        /// New title/name
+        #[arg(long)]
+        service: Option<String>,
+
+        /// New username
+        #[arg(long)]
+        username: Option<String>,
+
+        /// New URL
+        #[arg(short = 'u', long)]
+        url: Option<String>,
+
+        /// New notes
+        #[arg(short, long)]
+        notes: Option<String>,
+
+        /// Prompt for a new password
+        #[arg(long)]
+        password: bool,
+
+        /// Generate a new password that meets the site's password rules
+        #[arg(long, conflicts_with = "password")]
+        regenerate: bool,
+
+        /// Set the site password rules ("" to remove them)
+        #[arg(long, value_name = "RULES")]
+        password_rules: Option<String>,
+
+        /// Add or update a custom field as name=value (repeatable)
+        #[arg(long = "field", value_name = "NAME=VALUE")]
+        fields: Vec<String>,
+
+        /// Add or update a concealed custom field, prompted if no value given (repeatable)
+        #[arg(long = "secret-field", value_name = "NAME[=VALUE]")]
+        secret_fields: Vec<String>,
+
+        /// Remove a custom field by name (repeatable)
+        #[arg(long = "remove-field", value_name = "NAME")]
+        remove_fields: Vec<String>,
     },
 
     /// List all credentials
@@ -48,6 +136,54 @@ pub enum Commands {
         /// Show Passwords (hidden by default)
         #[arg(short, long)]
         show_password: bool,
+
+        /// Only list items of this type
+        #[arg(long = "type")]
+        kind: Option<ItemKind>,
+
+        /// Only list credentials in this folder ("/" for the vault root)
+        #[arg(short, long)]
+        folder: Option<String>,
+
+        /// Include subfolders of --folder
+        #[arg(short, long)]
+        recursive: bool,
+
+        /// Show credentials as a folder tree
+        #[arg(short, long)]
+        tree: bool,
+
+        /// Only list credentials matching a query, e.g. "tag:prod updated:<90d -tag:old"
+        #[arg(long)]
+        filter: Option<String>,
+
+        /// Sort by service, username, created or updated
+        #[arg(long)]
+        sort: Option<SortKey>,
+
+        /// Reverse the order
+        #[arg(long)]
+        reverse: bool,
+
+        /// Show at most this many credentials
+        #[arg(short, long)]
+        limit: Option<usize>,
+
+        /// Skip this many credentials first
+        #[arg(long, default_value_t = 0)]
+        offset: usize,
+
+        /// One line per credential
+        #[arg(long, conflicts_with = "tree")]
+        table: bool,
+
+        /// Table columns, e.g. service,username,tags (implies --table)
+        #[arg(long, value_delimiter = ',', conflicts_with = "tree")]
+        columns: Option<Vec<Column>>,
+
+        /// Print everything instead of using a pager
+        #[arg(long)]
+        no_pager: bool,
     },
 
     /// Get a Specific Credential
@@ -56,8 +192,32 @@ pub enum Commands {
         query: String,
 
         /// Copy password to clipboard
-        #[arg(short, long)]
+        #[arg(short, long, group = "copying")]
         copy: bool,
+
+        /// Copy another field instead: username, url, notes, otp or a custom field name
+        #[arg(long, value_name = "FIELD", group = "copying")]
+        copy_field: Option<String>,
+
+        /// Copy the username, then the password after a keypress
+        #[arg(long, group = "copying")]
+        copy_login: bool,
+
+        /// Print only the raw value of a field (password, username, otp, ...) for scripts
+        #[arg(long, value_name = "FIELD", conflicts_with = "copying")]
+        print_field: Option<String>,
+
+        /// Clear the clipboard after this many seconds (0 = never; default 30 or the config value)
+        #[arg(long, value_name = "SECS", requires = "copying")]
+        clear_after: Option<u64>,
+
+        /// auto, native, osc52 (terminal escape, works over SSH) or command (e.g. wl-copy)
+        #[arg(long, requires = "copying")]
+        clipboard_backend: Option<BackendChoice>,
+
+        /// Show concealed custom field values
+        #[arg(short, long)]
+        reveal: bool,
     },
 
     /// Delete a credential
@@ -70,10 +230,24 @@ pub enum Commands {
         force: bool,
     },
 
+    /// Browse the vault in a full-screen terminal UI
+    Tui,
+
+    /// Unlock once and run get/search/add/delete/list at a prompt
+    Shell {
+        /// Lock the session after this many minutes without input (0 = never)
+        #[arg(long, default_value_t = 5)]
+        timeout: u64,
+    },
+
     /// Search credentials by term
     Search {
-        /// Search term
+        /// Search term, or a query like "tag:prod url:*.example.com -tag:old"
         term: String,
+
+        /// Only match the term as a plain substring (no fuzzy matching)
+        #[arg(short, long)]
+        exact: bool,
     },
 
     /// Change master Password
@@ -81,12 +255,415 @@ pub enum Commands {
 
     /// Show vault statistics
     Status,
+
+    /// Generate passwords or passphrases, one per line (no vault needed)
+    Generate {
+        /// Password length, kept within --min-length and --max-length
+        #[arg(short, long, default_value_t = DEFAULT_LENGTH)]
+        length: usize,
+
+        /// Number of passwords to generate
+        #[arg(short = 'n', long, default_value_t = 1)]
+        count: usize,
+
+        /// Copy the password to the clipboard instead of printing it
+        #[arg(short, long)]
+        copy: bool,
+
+        /// Show the strength of each password (on stderr)
+        #[arg(long)]
+        strength: bool,
+
+        #[command(flatten)]
+        policy: PolicyFlags,
+
+        #[command(flatten)]
+        passphrase: PassphraseFlags,
+    },
+
+    /// Check every password against a local Have I Been Pwned SHA-1 file
+    BreachCheck {
+        /// Hash file sorted by hash (defaults to "hibp_file" in config.json)
+        #[arg(long, value_name = "PATH")]
+        hibp_file: Option<PathBuf>,
+    },
+
+    /// Check the vault for security problems
+    Audit {
+        #[command(subcommand)]
+        action: AuditAction,
+    },
+
+    /// Show credential differences against another vault file
+    Diff {
+        /// Path to the other vault file
+        other: PathBuf,
+    },
+
+    /// Merge credentials from another vault file into this vault
+    Merge {
+        /// Path to the other vault file
+        other: PathBuf,
+    },
+
+    /// Sync the encrypted vault with a WebDAV or S3-compatible remote
+    Sync {
+        /// Sync profile to use
+        #[arg(short, long, default_value = "default", global = true)]
+        profile: String,
+
+        #[command(subcommand)]
+        action: Option<SyncAction>,
+    },
+
+    /// Import credentials from a KeePassXC CSV or Bitwarden JSON export
+    Import {
+        /// Export file to read
+        file: PathBuf,
+
+        /// keepass-csv or bitwarden-json (guessed from the extension by default)
+        #[arg(long)]
+        format: Option<ImportFormat>,
+
+        /// Folder to import into; the export's groups become subfolders
+        #[arg(short, long)]
+        folder: Option<String>,
+    },
+
+    /// Move a credential (or a whole folder) to another folder
+    Mv {
+        /// Search term (title, username, or ID), or a folder path with --folder
+        query: String,
+
+        /// Destination folder path ("/" for the vault root)
+        destination: String,
+
+        /// Treat the query as a folder and move everything inside it
+        #[arg(short, long)]
+        folder: bool,
+    },
+
+    /// Add, remove, rename and count tags
+    Tag {
+        #[command(subcommand)]
+        action: TagAction,
+    },
+
+    /// Manage encrypted file attachments on a credential
+    Attach {
+        #[command(subcommand)]
+        action: AttachAction,
+    },
+
+    /// Clear the clipboard later if it still holds a copied secret (started by --copy)
+    #[command(hide = true)]
+    ClipboardClear {
+        /// Seconds to wait first
+        #[arg(long)]
+        after: u64,
+
+        #[arg(long, default_value = "auto")]
+        backend: BackendChoice,
+
+        #[arg(long)]
+        copy_command: Option<String>,
+
+        #[arg(long)]
+        paste_command: Option<String>,
+    },
+
+    /// Version the vault with git and sync it through a remote
+    Git {
+        #[command(subcommand)]
+        action: GitAction,
+    },
+}
+
+/// Diceware passphrase settings shared by the commands that generate passwords
+#[derive(Args)]
+pub struct PassphraseFlags {
+    /// Generate a passphrase of random words instead of random characters
+    #[arg(long)]
+    passphrase: bool,
+
+    /// Number of words in the passphrase
+    #[arg(long, default_value_t = 6, requires = "passphrase")]
+    words: usize,
+
+    /// Text between the words
+    #[arg(long, default_value = "-", requires = "passphrase")]
+    separator: String,
+
+    /// Capitalise the first letter of every word
+    #[arg(long, requires = "passphrase")]
+    capitalize: bool,
+
+    /// Add a random digit to one of the words
+    #[arg(long, requires = "passphrase")]
+    digit: bool,
+
+    /// Add a random symbol to one of the words
+    #[arg(long, requires = "passphrase")]
+    symbol: bool,
+
+    /// Word file to use instead of the built-in EFF list (one word per line)
+    #[arg(long, value_name = "PATH", requires = "passphrase")]
+    wordlist: Option<PathBuf>,
+}
+
+impl PassphraseFlags {
+    fn options(self) -> Option<PassphraseOptions> {
+        self.passphrase.then_some(PassphraseOptions {
+            words: self.words,
+            separator: self.separator,
+            capitalize: self.capitalize,
+            digit: self.digit,
+            symbol: self.symbol,
+            wordlist: self.wordlist,
+        })
+    }
+}
+
+/// Password policy settings for generated passwords
+#[derive(Args)]
+pub struct PolicyFlags {
+    /// Include symbols
+    #[arg(short, long)]
+    symbols: bool,
+
+    /// Allowed symbols, e.g. "-_!" (implies --symbols)
+    #[arg(long, value_name = "CHARS", allow_hyphen_values = true)]
+    symbol_set: Option<String>,
+
+    /// Shortest password the site accepts
+    #[arg(long, default_value_t = 8)]
+    min_length: usize,
+
+    /// Longest password the site accepts
+    #[arg(long, default_value_t = 128)]
+    max_length: usize,
+
+    /// At least this many lowercase letters
+    #[arg(long, value_name = "N", default_value_t = 0)]
+    min_lower: usize,
+
+    /// At least this many uppercase letters
+    #[arg(long, value_name = "N", default_value_t = 0)]
+    min_upper: usize,
+
+    /// At least this many digits
+    #[arg(long, value_name = "N", default_value_t = 0)]
+    min_digits: usize,
+
+    /// At least this many symbols (implies --symbols)
+    #[arg(long, value_name = "N", default_value_t = 0)]
+    min_symbols: usize,
+
+    /// Leave out look-alike characters (0 O 1 l I)
+    #[arg(long)]
+    no_look_alikes: bool,
+
+    /// Never repeat a character twice in a row
+    #[arg(long, conflicts_with = "max_consecutive")]
+    no_repeats: bool,
+
+    /// Longest run of one repeated character
+    #[arg(long, value_name = "N")]
+    max_consecutive: Option<usize>,
+}
+
+impl PolicyFlags {
+    fn policy(self) -> Policy {
+        let symbols = match self.symbol_set {
+            Some(set) => set,
+            None if self.symbols || self.min_symbols > 0 => DEFAULT_SYMBOLS.to_string(),
+            None => String::new(),
+        };
+        Policy {
+            min_length: self.min_length,
+            max_length: self.max_length,
+            min_lower: self.min_lower,
+            min_upper: self.min_upper,
+            min_digits: self.min_digits,
+            min_symbols: self.min_symbols,
+            symbols,
+            exclude_look_alikes: self.no_look_alikes,
+            max_consecutive: if self.no_repeats {
+                Some(1)
+            } else {
+                self.max_consecutive
+            },
+            ..Default::default()
+        }
+    }
+}
+
+#[derive(Subcommand)]
+pub enum SyncAction {
+    /// Configure the remote for a sync profile
+    Setup,
+}
+
+#[derive(Subcommand)]
+pub enum AuditAction {
+    /// Report reused, weak and old passwords, http:// URLs and logins without 2FA.
+    /// Exits with an error when anything is found, so CI can gate on it.
+    Passwords {
+        /// Only run these checks: reused, weak, old, insecure-url, missing-2fa
+        #[arg(long, value_delimiter = ',')]
+        checks: Vec<Check>,
+
+        /// Days since the last update after which a password counts as old
+        #[arg(long, value_name = "DAYS", default_value_t = DEFAULT_MAX_AGE)]
+        max_age: i64,
+
+        /// Print the report as JSON
+        #[arg(long)]
+        json: bool,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum TagAction {
+    /// Tag a credential, or with --all every credential matching a search
+    Add {
+        /// Search term (title, username, or ID)
+        query: String,
+
+        /// Tags to add
+        #[arg(required = true)]
+        tags: Vec<String>,
+
+        /// Apply to every credential matching the search term
+        #[arg(short, long)]
+        all: bool,
+    },
+
+    /// Remove tags from a credential, or with --all from every search match
+    Remove {
+        /// Search term (title, username, or ID)
+        query: String,
+
+        /// Tags to remove
+        #[arg(required = true)]
+        tags: Vec<String>,
+
+        /// Apply to every credential matching the search term
+        #[arg(short, long)]
+        all: bool,
+    },
+
+    /// Rename a tag across the vault
+    Rename {
+        /// Current tag name
+        old: String,
+
+        /// New tag name
+        new: String,
+
+        /// Only rename on credentials matching this search term
+        #[arg(short, long)]
+        search: Option<String>,
+    },
+
+    /// Show every tag with the number of credentials using it
+    List {
+        /// Only count credentials matching this search term
+        #[arg(short, long)]
+        search: Option<String>,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum AttachAction {
+    /// Encrypt a file and attach it to a credential
+    Add {
+        /// Search term (title, username, or ID)
+        query: String,
+
+        /// File to attach
+        file: PathBuf,
+
+        /// Name to store it under (defaults to the file name)
+        #[arg(long)]
+        name: Option<String>,
+    },
+
+    /// List a credential's attachments
+    List {
+        /// Search term (title, username, or ID)
+        query: String,
+    },
+
+    /// Decrypt an attachment to a file
+    Extract {
+        /// Search term (title, username, or ID)
+        query: String,
+
+        /// Attachment name
+        name: String,
+
+        /// Where to write it (defaults to the attachment name)
+        #[arg(short, long)]
+        output: Option<PathBuf>,
+
+        /// Overwrite an existing file
+        #[arg(short, long)]
+        force: bool,
+    },
+
+    /// Remove an attachment
+    Remove {
+        /// Search term (title, username, or ID)
+        query: String,
+
+        /// Attachment name
+        name: String,
+
+        /// Skip confirmation
+        #[arg(short, long)]
+        force: bool,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum GitAction {
+    /// Turn the vault directory into a git repository
+    Init {
+        /// Remote URL to push to and pull from (can be a local bare repo)
+        #[arg(short, long)]
+        remote: Option<String>,
+    },
+
+    /// Commit pending vault changes
+    Commit {
+        /// Commit message
+        #[arg(short, long)]
+        message: Option<String>,
+    },
+
+    /// Push vault history to the remote
+    Push,
+
+    /// Pull vault history, merging diverged vaults by credential
+    Pull,
+
+    /// Show vault history
+    Log {
+        /// Number of entries to show
+        #[arg(short = 'n', long, default_value_t = 20)]
+        limit: usize,
+    },
 }
 
 fn main() -> Result<()> {
     let cli = Cli::parse();
+    run(cli.command)
+}
 
-    match cli.command {
+/// Run one subcommand (also used for each line of `pwdmgr shell`)
+pub fn run(command: Commands) -> Result<()> {
+    match command {
         Commands::Init => commands::init::run(),
         Commands::Add {
             title,
@@ -94,12 +671,184 @@ fn main() -> Result<()> {
             url,
             notes,
             generate,
-        } => commands::add::run(title, username, url, notes, generate),
-        Commands::List { show_password } => commands::list::run(show_password),
-        Commands::Get { query, copy } => commands::get::run(query, copy),
+            kind,
+            folder,
+            tags,
+            fields,
+            secret_fields,
+            hibp_file,
+            password_rules,
+            passphrase,
+        } => commands::add::run(
+            title,
+            commands::add::AddArgs {
+                username,
+                url,
+                notes,
+                generate,
+                kind,
+                folder,
+                tags,
+                fields,
+                secret_fields,
+                hibp_file,
+                password_rules,
+                passphrase: passphrase.options(),
+            },
+        ),
+        Commands::Edit {
+            query,
+            service,
+            username,
+            url,
+            notes,
+            password,
+            regenerate,
+            password_rules,
+            fields,
+            secret_fields,
+            remove_fields,
+        } => commands::edit::run(
+            query,
+            commands::edit::EditArgs {
+                service,
+                username,
+                url,
+                notes,
+                password,
+                regenerate,
+                password_rules,
+                fields,
+                secret_fields,
+                remove_fields,
+            },
+        ),
+        Commands::List {
+            show_password,
+            kind,
+            folder,
+            recursive,
+            tree,
+            filter,
+            sort,
+            reverse,
+            limit,
+            offset,
+            table,
+            columns,
+            no_pager,
+        } => commands::list::run(ListArgs {
+            show_password,
+            kind,
+            folder,
+            recursive,
+            tree,
+            filter,
+            sort,
+            reverse,
+            limit,
+            offset,
+            table,
+            columns,
+            no_pager,
+        }),
+        Commands::Get {
+            query,
+            copy,
+            copy_field,
+            copy_login,
+            print_field,
+            clear_after,
+            clipboard_backend,
+            reveal,
+        } => commands::get::run(
+            query,
+            GetArgs {
+                copy,
+                copy_field,
+                copy_login,
+                print_field,
+                clear_after,
+                clipboard_backend,
+                reveal,
+            },
+        ),
         Commands::Delete { query, force } => commands::delete::run(query, force),
-        Commands::Search { term } => commands::search::run(term),
+        Commands::Search { term, exact } => commands::search::run(term, exact),
+        Commands::Tui => commands::tui::run(),
+        Commands::Shell { timeout } => commands::shell::run(timeout),
+        Commands::ClipboardClear {
+            after,
+            backend,
+            copy_command,
+            paste_command,
+        } => utils::clipboard::clear_later(after, backend, copy_command, paste_command),
         Commands::Changepassword => commands::change_password::run(),
         Commands::Status => commands::status::run(),
+        Commands::Generate {
+            length,
+            count,
+            copy,
+            strength,
+            policy,
+            passphrase,
+        } => commands::generate::run(GenerateArgs {
+            length,
+            policy: policy.policy(),
+            passphrase: passphrase.options(),
+            count,
+            copy,
+            strength,
+        }),
+        Commands::BreachCheck { hibp_file } => commands::breach_check::run(hibp_file),
+        Commands::Audit { action } => match action {
+            AuditAction::Passwords {
+                checks,
+                max_age,
+                json,
+            } => commands::audit::passwords(checks, max_age, json),
+        },
+        Commands::Diff { other } => commands::diff::run(other),
+        Commands::Merge { other } => commands::merge::run(other),
+        Commands::Sync { profile, action } => match action {
+            Some(SyncAction::Setup) => commands::sync::setup(profile),
+            None => commands::sync::run(profile),
+        },
+        Commands::Import {
+            file,
+            format,
+            folder,
+        } => commands::import::run(file, format, folder),
+        Commands::Mv {
+            query,
+            destination,
+            folder,
+        } => commands::mv::run(query, destination, folder),
+        Commands::Tag { action } => match action {
+            TagAction::Add { query, tags, all } => commands::tag::add(query, tags, all),
+            TagAction::Remove { query, tags, all } => commands::tag::remove(query, tags, all),
+            TagAction::Rename { old, new, search } => commands::tag::rename(old, new, search),
+            TagAction::List { search } => commands::tag::list(search),
+        },
+        Commands::Attach { action } => match action {
+            AttachAction::Add { query, file, name } => commands::attach::add(query, file, name),
+            AttachAction::List { query } => commands::attach::list(query),
+            AttachAction::Extract {
+                query,
+                name,
+                output,
+                force,
+            } => commands::attach::extract(query, name, output, force),
+            AttachAction::Remove { query, name, force } => {
+                commands::attach::remove(query, name, force)
+            }
+        },
+        Commands::Git { action } => match action {
+            GitAction::Init { remote } => commands::git::init(remote),
+            GitAction::Commit { message } => commands::git::commit(message),
+            GitAction::Push => commands::git::push(),
+            GitAction::Pull => commands::git::pull(),
+            GitAction::Log { limit } => commands::git::log(limit),
+        },
     }
 }
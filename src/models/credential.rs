@@ -1,9 +1,16 @@
+use crate::models::item::{ItemDetails, ItemKind};
+use crate::utils::generator::{DEFAULT_SYMBOLS, Policy};
+use crate::utils::password_rules;
+use crate::utils::strength::{self, Strength};
+use crate::utils::totp::OTP_FIELD_NAMES;
 use crate::utils::{DateTime, Utc};
+use anyhow::Result;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 use uuid::Uuid;
 use zeroize::Zeroize;
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Credential {
     pub id: Uuid,
     pub service: String,
@@ -14,6 +21,42 @@ pub struct Credential {
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
     pub tags: Vec<String>,
+    /// Slash-separated folder path such as "work/aws/prod"; `None` is the vault root
+    #[serde(default)]
+    pub folder: Option<String>,
+    #[serde(default)]
+    pub fields: Vec<CustomField>,
+    /// Card, identity, SSH key, ... data; plain logins have none
+    #[serde(default)]
+    pub details: ItemDetails,
+    #[serde(default)]
+    pub attachments: Vec<Attachment>,
+    /// Site constraints in Apple's `passwordrules` syntax, used when generating
+    #[serde(default)]
+    pub password_rules: Option<String>,
+}
+
+/// User-defined extra field (API key, security question, PIN, ...)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct CustomField {
+    pub name: String,
+    pub value: String,
+    /// Concealed values are masked in output and wiped on drop
+    pub concealed: bool,
+}
+
+/// File kept as an encrypted blob next to the vault (see `storage::attachments`)
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Attachment {
+    pub id: Uuid,
+    pub name: String,
+    /// Plaintext size in bytes
+    pub size: u64,
+    /// SHA-256 of the plaintext, checked after decryption
+    pub sha256: String,
+    /// Per-attachment AES-256 key (base64), protected by the vault encryption
+    pub key: String,
+    pub created_at: DateTime<Utc>,
 }
 
 #[derive(Debug, Serialize, Deserialize)]
@@ -22,6 +65,9 @@ pub struct CredentialVault {
     pub created_at: DateTime<Utc>,
     pub updated_at: DateTime<Utc>,
     pub version: u32,
+    /// WebDAV password or S3 secret key per sync profile, kept out of config.json
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub sync_secrets: BTreeMap<String, String>,
 }
 
 impl Credential {
@@ -38,6 +84,43 @@ impl Credential {
             created_at: now,
             updated_at: now,
             tags: Vec::new(),
+            folder: None,
+            fields: Vec::new(),
+            details: ItemDetails::Login,
+            attachments: Vec::new(),
+            password_rules: None,
+        }
+    }
+
+    /// Create a typed item; the password stays empty unless set later
+    pub fn with_details(service: String, details: ItemDetails) -> Self {
+        let mut credential = Self::new(service, String::new());
+        credential.details = details;
+        credential
+    }
+
+    pub fn kind(&self) -> ItemKind {
+        self.details.kind()
+    }
+
+    /// What `get --copy` should put on the clipboard
+    pub fn primary_secret(&self) -> &str {
+        if self.password.is_empty() {
+            self.details.secret().unwrap_or("")
+        } else {
+            &self.password
+        }
+    }
+
+    /// What a generated password must meet: the site rules, or else
+    /// letters, digits and the default symbols
+    pub fn password_policy(&self) -> Result<Policy> {
+        match &self.password_rules {
+            Some(rules) => password_rules::parse(rules),
+            None => Ok(Policy {
+                symbols: DEFAULT_SYMBOLS.to_string(),
+                ..Default::default()
+            }),
         }
     }
 
@@ -45,30 +128,125 @@ impl Credential {
         self.url = Some(url);
         self
     }
-    #[allow(dead_code)]
-    pub fn with_notes(self, notes: String) -> Self {
-        let _ = self.notes == Some(notes);
+    pub fn with_username(mut self, username: String) -> Self {
+        self.username = Some(username);
         self
     }
 
-    #[allow(dead_code)]
-    pub fn with_tags(self, tags: Vec<String>) -> Self {
-        let _ = self.tags == tags;
+    pub fn with_notes(mut self, notes: String) -> Self {
+        self.notes = Some(notes);
         self
     }
 
+    pub fn with_folder(mut self, folder: Option<String>) -> Self {
+        self.folder = folder;
+        self
+    }
+
+    pub fn with_tags(mut self, tags: Vec<String>) -> Self {
+        for tag in tags {
+            self.add_tag(&tag);
+        }
+        self
+    }
+
+    /// Whether the credential carries `tag` (case-insensitive)
+    pub fn has_tag(&self, tag: &str) -> bool {
+        self.tags.iter().any(|t| t.eq_ignore_ascii_case(tag.trim()))
+    }
+
+    /// Add a tag unless it is blank or already present, returns true if added
+    pub fn add_tag(&mut self, tag: &str) -> bool {
+        let tag = tag.trim();
+        if tag.is_empty() || self.has_tag(tag) {
+            return false;
+        }
+        self.tags.push(tag.to_string());
+        true
+    }
+
+    /// Remove a tag, returns false if it was not present
+    pub fn remove_tag(&mut self, tag: &str) -> bool {
+        let before = self.tags.len();
+        self.tags.retain(|t| !t.eq_ignore_ascii_case(tag.trim()));
+        self.tags.len() != before
+    }
+
     #[allow(dead_code)]
     pub fn update_password(&mut self, new_password: String) {
         self.password = new_password;
         self.updated_at = Utc::now();
     }
 
+    /// Look up a custom field by name (case-insensitive)
+    pub fn field(&self, name: &str) -> Option<&CustomField> {
+        self.fields
+            .iter()
+            .find(|f| f.name.eq_ignore_ascii_case(name))
+    }
+
+    /// The `otp`/`totp` field holding a one-time password secret, if any
+    pub fn otp_field(&self) -> Option<&CustomField> {
+        OTP_FIELD_NAMES.iter().find_map(|name| self.field(name))
+    }
+
+    /// How guessable the password is, also checking it against the service and username
+    pub fn password_strength(&self) -> Strength {
+        let username = self.username.as_deref().unwrap_or_default();
+        strength::estimate(&self.password, &[&self.service, username])
+    }
+
+    /// Add a custom field, or replace the value of an existing one in place
+    pub fn set_field(&mut self, name: String, value: String, concealed: bool) {
+        match self
+            .fields
+            .iter_mut()
+            .find(|f| f.name.eq_ignore_ascii_case(&name))
+        {
+            Some(field) => {
+                field.value.zeroize();
+                field.value = value;
+                field.concealed = concealed;
+            }
+            None => self.fields.push(CustomField {
+                name,
+                value,
+                concealed,
+            }),
+        }
+    }
+
+    /// Remove a custom field, returns false if it did not exist
+    pub fn remove_field(&mut self, name: &str) -> bool {
+        match self
+            .fields
+            .iter()
+            .position(|f| f.name.eq_ignore_ascii_case(name))
+        {
+            Some(index) => {
+                let mut field = self.fields.remove(index);
+                field.value.zeroize();
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Look up an attachment by file name (case-insensitive)
+    pub fn attachment(&self, name: &str) -> Option<&Attachment> {
+        self.attachments
+            .iter()
+            .find(|a| a.name.eq_ignore_ascii_case(name))
+    }
+
     // Display credentail safely (hide password)
     pub fn display_safe(&self) -> String {
         format!(
-            "ID {}\nService: {}\nUsername: {}\nURL: {}\nCreated At: {}\nTags: {}",
+            "ID {}\nService: {}\nType: {}\nFolder: {}\nUsername: {}\nURL: {}\nCreated At: {}\nTags: {}",
             self.id,
             self.service,
+            self.kind(),
+            self.folder.as_deref().unwrap_or("/"),
             self.username.as_deref().unwrap_or("N/A"),
             self.url.as_deref().unwrap_or("N/A"),
             self.created_at.format("%Y-%m-%d %H:%M"),
@@ -77,21 +255,35 @@ impl Credential {
     }
 
     // check if Credential matches search terms
+    // (custom field names always, values only when not concealed)
     pub fn matches_search(&self, search_term: &str) -> bool {
         let search_lower = search_term.to_lowercase();
         self.service.to_lowercase().contains(&search_lower)
             || self
                 .username
                 .as_ref()
-                .map_or(false, |u| u.to_lowercase().contains(&search_lower))
+                .is_some_and(|u| u.to_lowercase().contains(&search_lower))
             || self
                 .url
                 .as_ref()
-                .map_or(false, |u| u.to_lowercase().contains(&search_lower))
+                .is_some_and(|u| u.to_lowercase().contains(&search_lower))
             || self
                 .tags
                 .iter()
                 .any(|tag| tag.to_lowercase().contains(&search_lower))
+            || self
+                .folder
+                .as_ref()
+                .is_some_and(|f| f.to_lowercase().contains(&search_lower))
+            || self.fields.iter().any(|field| {
+                field.name.to_lowercase().contains(&search_lower)
+                    || (!field.concealed && field.value.to_lowercase().contains(&search_lower))
+            })
+            || self
+                .details
+                .searchable_text()
+                .iter()
+                .any(|text| text.to_lowercase().contains(&search_lower))
     }
 }
 
@@ -104,6 +296,7 @@ impl CredentialVault {
             created_at: now,
             updated_at: now,
             version: 1,
+            sync_secrets: BTreeMap::new(),
         }
     }
 
@@ -124,7 +317,6 @@ impl CredentialVault {
     pub fn find_credential(&self, id: &Uuid) -> Option<&Credential> {
         self.credentials.iter().find(|c| c.id == *id)
     }
-    #[allow(dead_code)]
     pub fn find_credential_mut(&mut self, id: &Uuid) -> Option<&mut Credential> {
         self.credentials.iter_mut().find(|c| c.id == *id)
     }
@@ -143,6 +335,13 @@ impl Zeroize for Credential {
         if let Some(ref mut notes) = self.notes {
             notes.zeroize();
         }
+        for field in self.fields.iter_mut().filter(|f| f.concealed) {
+            field.value.zeroize();
+        }
+        self.details.zeroize();
+        for attachment in self.attachments.iter_mut() {
+            attachment.key.zeroize();
+        }
     }
 }
 
@@ -151,3 +350,56 @@ impl Drop for Credential {
         self.zeroize();
     }
 }
+
+impl Drop for CredentialVault {
+    fn drop(&mut self) {
+        for secret in self.sync_secrets.values_mut() {
+            secret.zeroize();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_set_field_replaces_in_place() {
+        let mut cred = Credential::new("Bank".into(), "secret".into());
+        cred.set_field("Account".into(), "123".into(), false);
+        cred.set_field("PIN".into(), "0000".into(), true);
+        cred.set_field("account".into(), "456".into(), false);
+
+        let names: Vec<_> = cred.fields.iter().map(|f| f.name.as_str()).collect();
+        assert_eq!(names, vec!["Account", "PIN"]);
+        assert_eq!(cred.field("ACCOUNT").unwrap().value, "456");
+        assert!(cred.remove_field("pin"));
+        assert!(!cred.remove_field("pin"));
+    }
+
+    #[test]
+    fn test_search_skips_concealed_values() {
+        let mut cred = Credential::new("Bank".into(), "secret".into());
+        cred.set_field("Account".into(), "DE1234".into(), false);
+        cred.set_field("PIN".into(), "9876".into(), true);
+
+        assert!(cred.matches_search("de12"));
+        assert!(cred.matches_search("pin"));
+        assert!(!cred.matches_search("9876"));
+    }
+
+    #[test]
+    fn test_tags_are_deduplicated_case_insensitively() {
+        let mut cred = Credential::new("Bank".into(), "secret".into()).with_tags(vec![
+            "Finance".into(),
+            " finance ".into(),
+            "".into(),
+        ]);
+        assert_eq!(cred.tags, vec!["Finance"]);
+
+        assert!(cred.add_tag("2fa"));
+        assert!(!cred.add_tag("2FA"));
+        assert!(cred.remove_tag("FINANCE"));
+        assert_eq!(cred.tags, vec!["2fa"]);
+    }
+}
@@ -0,0 +1,97 @@
+// Folder paths such as "work/aws/prod" used to organise credentials
+
+/// Clean up a user-supplied folder path. Empty paths (or "/") mean the vault root.
+pub fn normalize(path: &str) -> anyhow::Result<Option<String>> {
+    let mut segments = Vec::new();
+    for segment in path.split('/').map(str::trim) {
+        match segment {
+            "" => continue,
+            "." | ".." => {
+                return Err(anyhow::anyhow!(
+                    "❌ Folder path '{}' may not contain '.' or '..'",
+                    path
+                ));
+            }
+            s => segments.push(s),
+        }
+    }
+
+    if segments.is_empty() {
+        Ok(None)
+    } else {
+        Ok(Some(segments.join("/")))
+    }
+}
+
+/// Whether `folder` is `parent` itself, or (when recursive) anywhere below it
+pub fn contains(parent: Option<&str>, folder: Option<&str>, recursive: bool) -> bool {
+    match (parent, folder) {
+        (None, None) => true,
+        (None, Some(_)) => recursive,
+        (Some(_), None) => false,
+        (Some(parent), Some(folder)) => {
+            folder == parent
+                || (recursive
+                    && folder
+                        .strip_prefix(parent)
+                        .is_some_and(|rest| rest.starts_with('/')))
+        }
+    }
+}
+
+/// Move `folder` from under `from` to under `to`, keeping the part below `from`.
+/// Returns `None` when `folder` is not inside `from`; `Some(None)` is the vault root.
+pub fn rebase(folder: Option<&str>, from: &str, to: Option<&str>) -> Option<Option<String>> {
+    let rest = folder?.strip_prefix(from)?;
+    if !rest.is_empty() && !rest.starts_with('/') {
+        return None;
+    }
+
+    let rest = rest.trim_start_matches('/');
+    Some(match (to, rest.is_empty()) {
+        (None, true) => None,
+        (None, false) => Some(rest.to_string()),
+        (Some(to), true) => Some(to.to_string()),
+        (Some(to), false) => Some(format!("{}/{}", to, rest)),
+    })
+}
+
+/// Path segments, used to sort folders in tree order
+pub fn segments(folder: Option<&str>) -> Vec<&str> {
+    folder.map(|f| f.split('/').collect()).unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize() {
+        assert_eq!(
+            normalize(" /work// aws/prod/ ").unwrap().as_deref(),
+            Some("work/aws/prod")
+        );
+        assert_eq!(normalize("/").unwrap(), None);
+        assert!(normalize("work/../root").is_err());
+    }
+
+    #[test]
+    fn test_contains() {
+        assert!(contains(Some("work"), Some("work"), false));
+        assert!(!contains(Some("work"), Some("work/aws"), false));
+        assert!(contains(Some("work"), Some("work/aws"), true));
+        assert!(!contains(Some("work"), Some("workshop"), true));
+        assert!(contains(None, Some("work"), true));
+        assert!(!contains(None, Some("work"), false));
+    }
+
+    #[test]
+    fn test_rebase() {
+        assert_eq!(
+            rebase(Some("work/aws/prod"), "work/aws", Some("cloud")),
+            Some(Some("cloud/prod".to_string()))
+        );
+        assert_eq!(rebase(Some("work/aws"), "work/aws", None), Some(None));
+        assert_eq!(rebase(Some("work/awsx"), "work/aws", Some("cloud")), None);
+    }
+}
@@ -0,0 +1,439 @@
+use crate::models::credential::{Credential, CredentialVault};
+use crate::utils::Utc;
+use uuid::Uuid;
+
+/// Summary of what a merge changed relative to our side
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct MergeReport {
+    pub added: usize,
+    pub updated: usize,
+    pub removed: usize,
+    pub conflicts: usize,
+}
+
+/// How a conflicting pair of credentials should be settled
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Resolution {
+    KeepOurs,
+    TakeTheirs,
+    /// Keep ours and add theirs as a separate credential
+    KeepBoth,
+}
+
+/// A single field that differs between two versions of a credential
+#[derive(Debug, PartialEq)]
+pub struct FieldChange {
+    pub field: String,
+    pub ours: Option<String>,
+    pub theirs: Option<String>,
+    /// Secret values must be masked when displayed
+    pub secret: bool,
+}
+
+/// Difference between two vaults for one credential id
+#[derive(Debug)]
+pub enum CredentialChange<'a> {
+    /// Only present in the other vault
+    Added(&'a Credential),
+    /// Only present in our vault
+    Removed(&'a Credential),
+    Changed {
+        ours: &'a Credential,
+        fields: Vec<FieldChange>,
+    },
+}
+
+/// Compare two vaults by credential `id`, listing what `theirs` adds, lacks or changes
+pub fn diff_vaults<'a>(
+    ours: &'a CredentialVault,
+    theirs: &'a CredentialVault,
+) -> Vec<CredentialChange<'a>> {
+    let mut changes = Vec::new();
+
+    for our_cred in &ours.credentials {
+        match theirs.find_credential(&our_cred.id) {
+            Some(their_cred) => {
+                let fields = changed_fields(our_cred, their_cred);
+                if !fields.is_empty() {
+                    changes.push(CredentialChange::Changed {
+                        ours: our_cred,
+                        fields,
+                    });
+                }
+            }
+            None => changes.push(CredentialChange::Removed(our_cred)),
+        }
+    }
+
+    for their_cred in &theirs.credentials {
+        if ours.find_credential(&their_cred.id).is_none() {
+            changes.push(CredentialChange::Added(their_cred));
+        }
+    }
+
+    changes
+}
+
+/// Fields that differ between two versions of the same credential
+pub fn changed_fields(ours: &Credential, theirs: &Credential) -> Vec<FieldChange> {
+    let mut fields = Vec::new();
+    let mut compare = |field: &str, a: Option<&str>, b: Option<&str>, secret: bool| {
+        if a != b {
+            fields.push(FieldChange {
+                field: field.to_string(),
+                ours: a.map(str::to_string),
+                theirs: b.map(str::to_string),
+                secret,
+            });
+        }
+    };
+
+    compare("service", Some(&ours.service), Some(&theirs.service), false);
+    compare(
+        "username",
+        ours.username.as_deref(),
+        theirs.username.as_deref(),
+        false,
+    );
+    compare(
+        "password",
+        Some(&ours.password),
+        Some(&theirs.password),
+        true,
+    );
+    compare("url", ours.url.as_deref(), theirs.url.as_deref(), false);
+    compare(
+        "folder",
+        ours.folder.as_deref(),
+        theirs.folder.as_deref(),
+        false,
+    );
+    compare(
+        "notes",
+        ours.notes.as_deref(),
+        theirs.notes.as_deref(),
+        true,
+    );
+
+    compare(
+        "password rules",
+        ours.password_rules.as_deref(),
+        theirs.password_rules.as_deref(),
+        false,
+    );
+
+    let (our_tags, their_tags) = (ours.tags.join(", "), theirs.tags.join(", "));
+    compare("tags", Some(&our_tags), Some(&their_tags), false);
+
+    let mut names: Vec<&str> = ours.fields.iter().map(|f| f.name.as_str()).collect();
+    for field in &theirs.fields {
+        if ours.field(&field.name).is_none() {
+            names.push(&field.name);
+        }
+    }
+    for name in names {
+        let (a, b) = (ours.field(name), theirs.field(name));
+        let secret = a.is_some_and(|f| f.concealed) || b.is_some_and(|f| f.concealed);
+        compare(
+            &format!("field '{}'", name),
+            a.map(|f| f.value.as_str()),
+            b.map(|f| f.value.as_str()),
+            secret,
+        );
+    }
+
+    let names = |c: &Credential| {
+        c.attachments
+            .iter()
+            .map(|a| format!("{} ({})", a.name, &a.sha256[..8.min(a.sha256.len())]))
+            .collect::<Vec<_>>()
+            .join(", ")
+    };
+    let (our_files, their_files) = (names(ours), names(theirs));
+    compare("attachments", Some(&our_files), Some(&their_files), false);
+
+    let (our_kind, their_kind) = (ours.kind().to_string(), theirs.kind().to_string());
+    compare("type", Some(&our_kind), Some(&their_kind), false);
+    let (our_details, their_details) = (ours.details.entries(), theirs.details.entries());
+    let mut labels: Vec<(&str, bool)> = our_details.iter().map(|(l, _, s)| (*l, *s)).collect();
+    for (label, _, secret) in &their_details {
+        if !labels.iter().any(|(l, _)| l == label) {
+            labels.push((label, *secret));
+        }
+    }
+    for (label, secret) in labels {
+        let value = |entries: &[(&str, String, bool)]| {
+            entries
+                .iter()
+                .find(|(l, _, _)| *l == label)
+                .map(|(_, v, _)| v.clone())
+        };
+        compare(
+            &label.to_lowercase(),
+            value(&our_details).as_deref(),
+            value(&their_details).as_deref(),
+            secret,
+        );
+    }
+
+    fields
+}
+
+/// Default conflict policy: keep whichever version was updated last
+pub fn last_writer_wins(ours: &Credential, theirs: &Credential) -> Resolution {
+    if theirs.updated_at > ours.updated_at {
+        Resolution::TakeTheirs
+    } else {
+        Resolution::KeepOurs
+    }
+}
+
+/// Merge two diverged vaults credential by credential, keyed on `id`.
+///
+/// With a common `base` this is a three-way merge: one-sided edits and
+/// deletions are applied, and credentials edited on both sides are conflicts.
+/// Without a base nothing can be treated as deleted and differences are settled
+/// by last-writer-wins on `updated_at`, except when that timestamp can't be
+/// trusted to pick a side (equal timestamps, or both copies edited since
+/// creation); those are conflicts too. Every conflict is handed to `resolve`.
+pub fn merge_vaults(
+    base: Option<&CredentialVault>,
+    ours: &CredentialVault,
+    theirs: &CredentialVault,
+    mut resolve: impl FnMut(&Credential, &Credential) -> Resolution,
+) -> (CredentialVault, MergeReport) {
+    let mut report = MergeReport::default();
+    let mut merged = CredentialVault::new();
+    merged.created_at = ours.created_at.min(theirs.created_at);
+    merged.version = ours.version.max(theirs.version);
+
+    let in_base = |id: &Uuid| base.and_then(|b| b.find_credential(id));
+
+    for our_cred in &ours.credentials {
+        match theirs.find_credential(&our_cred.id) {
+            Some(their_cred) if their_cred == our_cred => {
+                merged.credentials.push(our_cred.clone());
+            }
+            Some(their_cred) => {
+                let resolution = match in_base(&our_cred.id) {
+                    Some(base_cred) if base_cred == our_cred => Resolution::TakeTheirs,
+                    Some(base_cred) if base_cred == their_cred => Resolution::KeepOurs,
+                    None if base.is_none() && !is_true_conflict(our_cred, their_cred) => {
+                        last_writer_wins(our_cred, their_cred)
+                    }
+                    _ => {
+                        report.conflicts += 1;
+                        resolve(our_cred, their_cred)
+                    }
+                };
+
+                match resolution {
+                    Resolution::KeepOurs => merged.credentials.push(our_cred.clone()),
+                    Resolution::TakeTheirs => {
+                        report.updated += 1;
+                        merged.credentials.push(their_cred.clone());
+                    }
+                    Resolution::KeepBoth => {
+                        report.added += 1;
+                        merged.credentials.push(our_cred.clone());
+                        let mut copy = their_cred.clone();
+                        copy.id = Uuid::new_v4();
+                        copy.service = format!("{} (conflict copy)", copy.service);
+                        merged.credentials.push(copy);
+                    }
+                }
+            }
+            None => match in_base(&our_cred.id) {
+                // Deleted on their side and untouched on ours
+                Some(base_cred) if base_cred == our_cred => report.removed += 1,
+                // Either new on our side or edited here after they deleted it
+                _ => merged.credentials.push(our_cred.clone()),
+            },
+        }
+    }
+
+    for their_cred in &theirs.credentials {
+        if ours.find_credential(&their_cred.id).is_some() {
+            continue;
+        }
+
+        match in_base(&their_cred.id) {
+            // Deleted on our side and untouched on theirs
+            Some(base_cred) if base_cred == their_cred => {}
+            _ => {
+                report.added += 1;
+                merged.credentials.push(their_cred.clone());
+            }
+        }
+    }
+
+    // Sync secrets are set up per profile; a profile known to both keeps ours
+    merged.sync_secrets = theirs.sync_secrets.clone();
+    merged.sync_secrets.extend(ours.sync_secrets.clone());
+
+    merged.updated_at = Utc::now();
+    (merged, report)
+}
+
+/// Without a common ancestor, decide whether `updated_at` alone is enough to
+/// pick a winner. If one side was never edited after creation the other side
+/// simply has newer data; if both were edited we can't tell what each lost.
+fn is_true_conflict(ours: &Credential, theirs: &Credential) -> bool {
+    let edited = |c: &Credential| c.updated_at != c.created_at;
+    ours.updated_at == theirs.updated_at || (edited(ours) && edited(theirs))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::Duration;
+
+    fn vault_with(creds: &[&Credential]) -> CredentialVault {
+        let mut vault = CredentialVault::new();
+        for cred in creds {
+            vault.add_credentail((*cred).clone());
+        }
+        vault
+    }
+
+    #[test]
+    fn test_changed_fields_covers_item_details() {
+        use crate::models::item::{CardDetails, ItemDetails};
+
+        let card = CardDetails::new(None, "4111111111111111", "01/30", "123").unwrap();
+        let ours = Credential::with_details("Visa".into(), ItemDetails::Card(card.clone()));
+        let mut theirs = ours.clone();
+        if let ItemDetails::Card(card) = &mut theirs.details {
+            card.cvv = "456".into();
+        }
+
+        let changes = changed_fields(&ours, &theirs);
+        assert_eq!(changes.len(), 1);
+        assert_eq!(changes[0].field, "cvv");
+        assert!(changes[0].secret);
+    }
+
+    fn edited(cred: &Credential, password: &str, minutes: i64) -> Credential {
+        let mut cred = cred.clone();
+        cred.password = password.to_string();
+        cred.updated_at += Duration::minutes(minutes);
+        cred
+    }
+
+    #[test]
+    fn test_one_sided_changes_are_applied() {
+        let github = Credential::new("GitHub".into(), "one".into());
+        let gitlab = Credential::new("GitLab".into(), "two".into());
+        let base = vault_with(&[&github, &gitlab]);
+
+        let ours = vault_with(&[&github, &gitlab]);
+        let new_cred = Credential::new("Email".into(), "three".into());
+        let theirs = vault_with(&[&edited(&github, "changed", 1), &new_cred]);
+
+        let (merged, report) = merge_vaults(Some(&base), &ours, &theirs, last_writer_wins);
+
+        assert_eq!(merged.credentials.len(), 2);
+        assert_eq!(
+            merged.find_credential(&github.id).unwrap().password,
+            "changed"
+        );
+        assert!(merged.find_credential(&gitlab.id).is_none());
+        assert!(merged.find_credential(&new_cred.id).is_some());
+        assert_eq!(
+            report,
+            MergeReport {
+                added: 1,
+                updated: 1,
+                removed: 1,
+                conflicts: 0
+            }
+        );
+    }
+
+    #[test]
+    fn test_concurrent_edits_use_last_writer() {
+        let github = Credential::new("GitHub".into(), "one".into());
+        let base = vault_with(&[&github]);
+        let ours = vault_with(&[&edited(&github, "ours", 5)]);
+        let theirs = vault_with(&[&edited(&github, "theirs", 2)]);
+
+        let (merged, report) = merge_vaults(Some(&base), &ours, &theirs, last_writer_wins);
+
+        assert_eq!(merged.find_credential(&github.id).unwrap().password, "ours");
+        assert_eq!(report.conflicts, 1);
+        assert_eq!(report.updated, 0);
+    }
+
+    #[test]
+    fn test_without_base_nothing_is_removed() {
+        let github = Credential::new("GitHub".into(), "one".into());
+        let gitlab = Credential::new("GitLab".into(), "two".into());
+        let ours = vault_with(&[&github]);
+        let theirs = vault_with(&[&gitlab]);
+
+        let (merged, report) = merge_vaults(None, &ours, &theirs, last_writer_wins);
+
+        assert_eq!(merged.credentials.len(), 2);
+        assert_eq!(report.added, 1);
+        assert_eq!(report.removed, 0);
+    }
+
+    #[test]
+    fn test_sync_secrets_are_kept_from_both_sides() {
+        let mut ours = vault_with(&[]);
+        ours.sync_secrets.insert("home".into(), "ours".into());
+        ours.sync_secrets.insert("work".into(), "new key".into());
+        let mut theirs = vault_with(&[]);
+        theirs.sync_secrets.insert("work".into(), "old key".into());
+        theirs.sync_secrets.insert("s3".into(), "theirs".into());
+
+        let (merged, _) = merge_vaults(None, &ours, &theirs, last_writer_wins);
+
+        assert_eq!(merged.sync_secrets.len(), 3);
+        assert_eq!(merged.sync_secrets["work"], "new key");
+        assert_eq!(merged.sync_secrets["s3"], "theirs");
+    }
+
+    #[test]
+    fn test_without_base_only_double_edits_are_conflicts() {
+        let github = Credential::new("GitHub".into(), "one".into());
+        let gitlab = Credential::new("GitLab".into(), "two".into());
+        let ours = vault_with(&[&github, &edited(&gitlab, "ours", 1)]);
+        let theirs = vault_with(&[&edited(&github, "newer", 1), &edited(&gitlab, "theirs", 2)]);
+
+        let mut asked = Vec::new();
+        let (merged, report) = merge_vaults(None, &ours, &theirs, |ours, _| {
+            asked.push(ours.service.clone());
+            Resolution::KeepBoth
+        });
+
+        assert_eq!(asked, vec!["GitLab".to_string()]);
+        assert_eq!(
+            merged.find_credential(&github.id).unwrap().password,
+            "newer"
+        );
+        assert_eq!(merged.credentials.len(), 3);
+        assert_eq!(report.conflicts, 1);
+    }
+
+    #[test]
+    fn test_diff_reports_changed_fields() {
+        let github = Credential::new("GitHub".into(), "one".into());
+        let gitlab = Credential::new("GitLab".into(), "two".into());
+        let ours = vault_with(&[&github, &gitlab]);
+        let theirs = vault_with(&[&edited(&github, "changed", 1)]);
+
+        let changes = diff_vaults(&ours, &theirs);
+
+        assert_eq!(changes.len(), 2);
+        match &changes[0] {
+            CredentialChange::Changed { fields, .. } => {
+                assert_eq!(fields.len(), 1);
+                assert_eq!(fields[0].field, "password");
+                assert!(fields[0].secret);
+            }
+            other => panic!("unexpected change: {:?}", other),
+        }
+        assert!(matches!(changes[1], CredentialChange::Removed(c) if c.id == gitlab.id));
+    }
+}
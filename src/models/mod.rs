@@ -1 +1,6 @@
 pub mod credential;
+pub mod folder;
+pub mod item;
+pub mod merge;
+pub mod query;
+pub mod search;
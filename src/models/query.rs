@@ -0,0 +1,584 @@
+// Structured query language for `search` and `list --filter`, e.g.
+// `tag:prod url:*.aws.amazon.com updated:<90d -tag:deprecated`
+use crate::models::credential::Credential;
+use crate::models::folder;
+use crate::models::item::ItemKind;
+use crate::utils::{DateTime, Utc};
+use chrono::{Duration, NaiveDate};
+use std::cmp::Ordering;
+
+/// Parsed query; adjacent terms are ANDed, `OR` binds looser than AND
+#[derive(Debug, Clone, PartialEq)]
+pub enum Query {
+    And(Vec<Query>),
+    Or(Vec<Query>),
+    Not(Box<Query>),
+    Term(Term),
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Term {
+    /// Bare word: any searchable field
+    Text(Pattern),
+    Service(Pattern),
+    Username(Pattern),
+    /// Matches the whole URL or just its host
+    Url(Pattern),
+    Tag(Pattern),
+    /// Without a glob, also matches subfolders
+    Folder(Pattern),
+    Kind(ItemKind),
+    /// Credential has a custom field with this name
+    Field(Pattern),
+    Created(Comparison, DateBound),
+    Updated(Comparison, DateBound),
+}
+
+/// Text to look for; globs (`*`, `?`) must match the whole value, plain text is a substring
+#[derive(Debug, Clone, PartialEq)]
+pub struct Pattern {
+    text: String,
+    glob: bool,
+}
+
+/// Operator of a date term
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Comparison {
+    Less,
+    LessOrEqual,
+    Equal,
+    GreaterOrEqual,
+    Greater,
+}
+
+impl Comparison {
+    fn holds(&self, ordering: Ordering) -> bool {
+        match self {
+            Comparison::Less => ordering.is_lt(),
+            Comparison::LessOrEqual => ordering.is_le(),
+            Comparison::Equal => ordering.is_eq(),
+            Comparison::GreaterOrEqual => ordering.is_ge(),
+            Comparison::Greater => ordering.is_gt(),
+        }
+    }
+}
+
+/// Right-hand side of a date comparison
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum DateBound {
+    /// `90d`: compares the age of the timestamp
+    Age(Duration),
+    /// `2024-01-31`: compares against that calendar day (UTC)
+    Day(NaiveDate),
+}
+
+const FIELDS: &str = "service, username, url, tag, folder, type, field, created, updated";
+
+impl Query {
+    pub fn parse(input: &str) -> anyhow::Result<Self> {
+        let tokens = tokenize(input)?;
+        if tokens.is_empty() {
+            return Err(anyhow::anyhow!("❌ Empty query"));
+        }
+
+        let mut parser = Parser { tokens, pos: 0 };
+        let query = parser.parse_or()?;
+        if let Some(token) = parser.tokens.get(parser.pos) {
+            return Err(anyhow::anyhow!("❌ Unexpected '{}' in query", token.text()));
+        }
+        Ok(query)
+    }
+
+    /// The single plain word of a query like `github`, which gets fuzzy ranking instead
+    pub fn as_plain_text(&self) -> Option<&str> {
+        match self {
+            Query::Term(Term::Text(pattern)) if !pattern.glob => Some(&pattern.text),
+            _ => None,
+        }
+    }
+
+    pub fn matches(&self, credential: &Credential, now: DateTime<Utc>) -> bool {
+        match self {
+            Query::And(parts) => parts.iter().all(|q| q.matches(credential, now)),
+            Query::Or(parts) => parts.iter().any(|q| q.matches(credential, now)),
+            Query::Not(inner) => !inner.matches(credential, now),
+            Query::Term(term) => term.matches(credential, now),
+        }
+    }
+}
+
+impl Term {
+    fn matches(&self, c: &Credential, now: DateTime<Utc>) -> bool {
+        match self {
+            Term::Text(p) if p.glob => {
+                p.matches(&c.service)
+                    || c.username.as_deref().is_some_and(|u| p.matches(u))
+                    || c.url.as_deref().is_some_and(|u| p.matches(u))
+                    || c.tags.iter().any(|t| p.matches(t))
+            }
+            Term::Text(p) => c.matches_search(&p.text),
+            Term::Service(p) => p.matches(&c.service),
+            Term::Username(p) => c.username.as_deref().is_some_and(|u| p.matches(u)),
+            Term::Url(p) => c
+                .url
+                .as_deref()
+                .is_some_and(|u| p.matches(u) || p.matches(host(u))),
+            Term::Tag(p) if p.glob => c.tags.iter().any(|t| p.matches(t)),
+            Term::Tag(p) => c.has_tag(&p.text),
+            Term::Folder(p) if p.glob => c.folder.as_deref().is_some_and(|f| p.matches(f)),
+            Term::Folder(p) => {
+                let folder = c.folder.as_ref().map(|f| f.to_lowercase());
+                folder::contains(Some(&p.text), folder.as_deref(), true)
+            }
+            Term::Kind(kind) => c.kind() == *kind,
+            Term::Field(p) => c.fields.iter().any(|f| p.matches_whole(&f.name)),
+            Term::Created(op, bound) => compare_date(c.created_at, *op, bound, now),
+            Term::Updated(op, bound) => compare_date(c.updated_at, *op, bound, now),
+        }
+    }
+}
+
+impl Pattern {
+    fn new(text: &str) -> Self {
+        Self {
+            text: text.to_lowercase(),
+            glob: text.contains(['*', '?']),
+        }
+    }
+
+    fn matches(&self, value: &str) -> bool {
+        let value = value.to_lowercase();
+        if self.glob {
+            glob_match(&self.text, &value)
+        } else {
+            value.contains(&self.text)
+        }
+    }
+
+    /// Like `matches`, but plain text must equal the whole value
+    fn matches_whole(&self, value: &str) -> bool {
+        if self.glob {
+            self.matches(value)
+        } else {
+            value.to_lowercase() == self.text
+        }
+    }
+}
+
+/// Compare the timestamp's age (`now - timestamp`) or its day against the bound
+fn compare_date(
+    timestamp: DateTime<Utc>,
+    op: Comparison,
+    bound: &DateBound,
+    now: DateTime<Utc>,
+) -> bool {
+    match bound {
+        DateBound::Age(age) => op.holds((now - timestamp).cmp(age)),
+        DateBound::Day(day) => op.holds(timestamp.date_naive().cmp(day)),
+    }
+}
+
+fn host(url: &str) -> &str {
+    let rest = url.split_once("://").map_or(url, |(_, rest)| rest);
+    let authority = rest.split(['/', '?', '#']).next().unwrap_or(rest);
+    let authority = authority.rsplit_once('@').map_or(authority, |(_, h)| h);
+    authority.split(':').next().unwrap_or(authority)
+}
+
+/// `*` matches any run of characters, `?` exactly one
+fn glob_match(pattern: &str, text: &str) -> bool {
+    let pattern: Vec<char> = pattern.chars().collect();
+    let text: Vec<char> = text.chars().collect();
+    let (mut p, mut t) = (0, 0);
+    let mut backtrack: Option<(usize, usize)> = None;
+
+    while t < text.len() {
+        match pattern.get(p) {
+            Some('*') => {
+                backtrack = Some((p, t));
+                p += 1;
+            }
+            Some(&c) if c == '?' || c == text[t] => {
+                p += 1;
+                t += 1;
+            }
+            _ => match backtrack {
+                // Let the last `*` swallow one more character and retry
+                Some((star, matched)) => {
+                    p = star + 1;
+                    t = matched + 1;
+                    backtrack = Some((star, matched + 1));
+                }
+                None => return false,
+            },
+        }
+    }
+
+    pattern[p..].iter().all(|&c| c == '*')
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    Word { text: String, negated: bool },
+    And,
+    Or,
+    Not,
+    Open,
+    Close,
+}
+
+impl Token {
+    fn text(&self) -> String {
+        match self {
+            Token::Word { text, negated } => {
+                format!("{}{}", if *negated { "-" } else { "" }, text)
+            }
+            Token::And => "AND".into(),
+            Token::Or => "OR".into(),
+            Token::Not => "NOT".into(),
+            Token::Open => "(".into(),
+            Token::Close => ")".into(),
+        }
+    }
+}
+
+fn tokenize(input: &str) -> anyhow::Result<Vec<Token>> {
+    let mut tokens = Vec::new();
+    let mut chars = input.chars().peekable();
+
+    while let Some(&c) = chars.peek() {
+        match c {
+            c if c.is_whitespace() => {
+                chars.next();
+            }
+            '(' => {
+                chars.next();
+                tokens.push(Token::Open);
+            }
+            ')' => {
+                chars.next();
+                tokens.push(Token::Close);
+            }
+            _ => {
+                let negated = c == '-';
+                if negated {
+                    chars.next();
+                }
+
+                // Read up to whitespace or a paren; quotes may wrap spaces
+                let mut text = String::new();
+                let mut quoted = false;
+                while let Some(&c) = chars.peek() {
+                    if c == '"' {
+                        quoted = !quoted;
+                    } else if !quoted && (c.is_whitespace() || c == '(' || c == ')') {
+                        break;
+                    } else {
+                        text.push(c);
+                    }
+                    chars.next();
+                }
+                if quoted {
+                    return Err(anyhow::anyhow!("❌ Unterminated quote in query"));
+                }
+
+                tokens.push(match text.as_str() {
+                    "AND" if !negated => Token::And,
+                    "OR" if !negated => Token::Or,
+                    "NOT" if !negated => Token::Not,
+                    "" if negated => {
+                        return Err(anyhow::anyhow!("❌ '-' must be followed by a term"));
+                    }
+                    _ => Token::Word { text, negated },
+                });
+            }
+        }
+    }
+
+    Ok(tokens)
+}
+
+struct Parser {
+    tokens: Vec<Token>,
+    pos: usize,
+}
+
+impl Parser {
+    fn peek(&self) -> Option<&Token> {
+        self.tokens.get(self.pos)
+    }
+
+    fn parse_or(&mut self) -> anyhow::Result<Query> {
+        let mut parts = vec![self.parse_and()?];
+        while self.peek() == Some(&Token::Or) {
+            self.pos += 1;
+            parts.push(self.parse_and()?);
+        }
+        Ok(if parts.len() == 1 {
+            parts.remove(0)
+        } else {
+            Query::Or(parts)
+        })
+    }
+
+    fn parse_and(&mut self) -> anyhow::Result<Query> {
+        let mut parts = vec![self.parse_unary()?];
+        loop {
+            match self.peek() {
+                Some(Token::And) => {
+                    self.pos += 1;
+                    parts.push(self.parse_unary()?);
+                }
+                // Juxtaposition is an implicit AND
+                Some(Token::Word { .. }) | Some(Token::Not) | Some(Token::Open) => {
+                    parts.push(self.parse_unary()?);
+                }
+                _ => break,
+            }
+        }
+        Ok(if parts.len() == 1 {
+            parts.remove(0)
+        } else {
+            Query::And(parts)
+        })
+    }
+
+    fn parse_unary(&mut self) -> anyhow::Result<Query> {
+        let token = self
+            .tokens
+            .get(self.pos)
+            .cloned()
+            .ok_or_else(|| anyhow::anyhow!("❌ Query ends where a term was expected"))?;
+        self.pos += 1;
+
+        match token {
+            Token::Not => Ok(Query::Not(Box::new(self.parse_unary()?))),
+            Token::Open => {
+                let inner = self.parse_or()?;
+                if self.peek() != Some(&Token::Close) {
+                    return Err(anyhow::anyhow!("❌ Missing ')' in query"));
+                }
+                self.pos += 1;
+                Ok(inner)
+            }
+            Token::Word { text, negated } => {
+                let term = Query::Term(parse_term(&text)?);
+                Ok(if negated {
+                    Query::Not(Box::new(term))
+                } else {
+                    term
+                })
+            }
+            other => Err(anyhow::anyhow!("❌ Unexpected '{}' in query", other.text())),
+        }
+    }
+}
+
+fn parse_term(word: &str) -> anyhow::Result<Term> {
+    let Some((field, value)) = word.split_once(':') else {
+        return Ok(Term::Text(Pattern::new(word)));
+    };
+
+    // `https://...` and other non-field prefixes are plain text
+    if value.starts_with("//") || !field.chars().all(|c| c.is_ascii_alphabetic()) {
+        return Ok(Term::Text(Pattern::new(word)));
+    }
+    if value.is_empty() {
+        return Err(anyhow::anyhow!("❌ '{}:' needs a value", field));
+    }
+
+    let pattern = Pattern::new(value);
+    Ok(match field.to_lowercase().as_str() {
+        "service" | "title" | "name" => Term::Service(pattern),
+        "username" | "user" => Term::Username(pattern),
+        "url" | "host" => Term::Url(pattern),
+        "tag" => Term::Tag(pattern),
+        "folder" | "in" => Term::Folder(match folder::normalize(value)? {
+            Some(folder) => Pattern::new(&folder),
+            None => return Err(anyhow::anyhow!("❌ 'folder:' needs a folder path")),
+        }),
+        "type" | "kind" => Term::Kind(value.parse()?),
+        "field" => Term::Field(pattern),
+        "created" => {
+            let (op, bound) = parse_date(value)?;
+            Term::Created(op, bound)
+        }
+        "updated" | "modified" => {
+            let (op, bound) = parse_date(value)?;
+            Term::Updated(op, bound)
+        }
+        other => {
+            return Err(anyhow::anyhow!(
+                "❌ Unknown query field '{}' (expected {})",
+                other,
+                FIELDS
+            ));
+        }
+    })
+}
+
+/// `<90d` (younger than 90 days), `>=2w`, `2024-01-31`, `<2024-01-31` (before that day).
+/// A bare age means "within", a bare date means "on that day".
+fn parse_date(value: &str) -> anyhow::Result<(Comparison, DateBound)> {
+    let (op, rest) = [
+        ("<=", Comparison::LessOrEqual),
+        (">=", Comparison::GreaterOrEqual),
+        ("<", Comparison::Less),
+        (">", Comparison::Greater),
+        ("=", Comparison::Equal),
+    ]
+    .into_iter()
+    .find_map(|(prefix, op)| value.strip_prefix(prefix).map(|rest| (Some(op), rest)))
+    .unwrap_or((None, value));
+
+    let bound = match NaiveDate::parse_from_str(rest, "%Y-%m-%d") {
+        Ok(day) => DateBound::Day(day),
+        Err(_) => DateBound::Age(parse_age(rest)?),
+    };
+
+    let op = op.unwrap_or(match bound {
+        DateBound::Age(_) => Comparison::Less,
+        DateBound::Day(_) => Comparison::Equal,
+    });
+    Ok((op, bound))
+}
+
+fn parse_age(value: &str) -> anyhow::Result<Duration> {
+    let invalid = || {
+        anyhow::anyhow!(
+            "❌ '{}' is not a date (YYYY-MM-DD) or age like 12h, 90d, 2w, 6m, 1y",
+            value
+        )
+    };
+    let split = value
+        .find(|c: char| !c.is_ascii_digit())
+        .ok_or_else(invalid)?;
+    let (number, unit) = value.split_at(split);
+    let number: i64 = number.parse().map_err(|_| invalid())?;
+
+    // Ages past what a timestamp can hold are rejected instead of overflowing
+    match unit {
+        "h" => Duration::try_hours(number),
+        "d" => Duration::try_days(number),
+        "w" => Duration::try_weeks(number),
+        "m" => number.checked_mul(30).and_then(Duration::try_days),
+        "y" => number.checked_mul(365).and_then(Duration::try_days),
+        _ => None,
+    }
+    .ok_or_else(invalid)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn term(t: Term) -> Query {
+        Query::Term(t)
+    }
+
+    #[test]
+    fn test_parse_implicit_and_negation_and_or_precedence() {
+        let query = Query::parse("tag:prod url:*.aws.amazon.com OR -tag:deprecated").unwrap();
+        assert_eq!(
+            query,
+            Query::Or(vec![
+                Query::And(vec![
+                    term(Term::Tag(Pattern::new("prod"))),
+                    term(Term::Url(Pattern::new("*.aws.amazon.com"))),
+                ]),
+                Query::Not(Box::new(term(Term::Tag(Pattern::new("deprecated"))))),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_parse_groups_quotes_and_keywords() {
+        let query = Query::parse("NOT (service:\"my bank\" OR user:alice) AND type:card").unwrap();
+        assert_eq!(
+            query,
+            Query::And(vec![
+                Query::Not(Box::new(Query::Or(vec![
+                    term(Term::Service(Pattern::new("my bank"))),
+                    term(Term::Username(Pattern::new("alice"))),
+                ]))),
+                term(Term::Kind(ItemKind::Card)),
+            ])
+        );
+
+        // URLs are text, not a `https` field
+        assert_eq!(
+            Query::parse("https://github.com").unwrap().as_plain_text(),
+            Some("https://github.com")
+        );
+    }
+
+    #[test]
+    fn test_parse_dates() {
+        assert_eq!(
+            parse_date("<90d").unwrap(),
+            (Comparison::Less, DateBound::Age(Duration::days(90)))
+        );
+        assert_eq!(
+            parse_date(">=2w").unwrap(),
+            (
+                Comparison::GreaterOrEqual,
+                DateBound::Age(Duration::weeks(2))
+            )
+        );
+        assert_eq!(
+            parse_date("2024-01-31").unwrap(),
+            (
+                Comparison::Equal,
+                DateBound::Day(NaiveDate::from_ymd_opt(2024, 1, 31).unwrap())
+            )
+        );
+        assert!(parse_date("<90x").is_err());
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        assert!(Query::parse("").is_err());
+        assert!(Query::parse("(tag:prod").is_err());
+        assert!(Query::parse("tag:prod)").is_err());
+        assert!(Query::parse("colour:red").is_err());
+        assert!(Query::parse("tag:").is_err());
+        assert!(Query::parse("service:\"open").is_err());
+        assert!(Query::parse("tag:a OR").is_err());
+        assert!(Query::parse("type:spaceship").is_err());
+        assert!(Query::parse("updated:<99999999999999999d").is_err());
+        assert!(Query::parse("updated:<999999999999999999y").is_err());
+    }
+
+    #[test]
+    fn test_glob() {
+        assert!(glob_match("*.aws.amazon.com", "console.aws.amazon.com"));
+        assert!(glob_match("g?t*", "github"));
+        assert!(!glob_match("*.aws.amazon.com", "aws.amazon.com"));
+        assert!(glob_match("a*b*c", "axxbyyc"));
+        assert!(!glob_match("a*b*c", "axxbyy"));
+    }
+
+    #[test]
+    fn test_matches_credentials() {
+        let now = Utc::now();
+        let mut cred = Credential::new("AWS Console".into(), "pw".into())
+            .with_url("https://console.aws.amazon.com/home".into())
+            .with_folder(Some("Work/Cloud".into()))
+            .with_tags(vec!["Prod".into()]);
+        cred.updated_at = now - Duration::days(10);
+
+        let matches = |q: &str| Query::parse(q).unwrap().matches(&cred, now);
+        assert!(matches(
+            "tag:prod url:*.aws.amazon.com updated:<90d -tag:deprecated"
+        ));
+        assert!(matches("folder:work"));
+        assert!(!matches("folder:work/clo"));
+        assert!(matches("console OR tag:nothing"));
+        assert!(!matches("updated:>30d"));
+        assert!(!matches("tag:prod -service:aws*"));
+        assert!(matches(&format!(
+            "updated:{}",
+            cred.updated_at.format("%Y-%m-%d")
+        )));
+    }
+}
@@ -0,0 +1,172 @@
+// Ranked credential search built on utils::fuzzy
+use crate::models::credential::{Credential, CredentialVault};
+use crate::models::query::Query;
+use crate::utils::Utc;
+use crate::utils::fuzzy::{self, Match};
+
+/// Which credential field a search hit was found in
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SearchField {
+    Service,
+    Username,
+    Url,
+    Tag(usize),
+    Folder,
+}
+
+impl SearchField {
+    /// Service name matters most; tags and folders least
+    fn weight(&self) -> i32 {
+        match self {
+            SearchField::Service => 3,
+            SearchField::Username | SearchField::Url => 2,
+            SearchField::Tag(_) | SearchField::Folder => 1,
+        }
+    }
+}
+
+/// A credential with its search score and the characters to highlight
+#[derive(Debug)]
+pub struct SearchHit<'a> {
+    pub credential: &'a Credential,
+    pub score: i32,
+    pub matches: Vec<(SearchField, Match)>,
+}
+
+impl SearchHit<'_> {
+    /// Matched character positions within `field`, if it matched
+    pub fn positions(&self, field: SearchField) -> &[usize] {
+        self.matches
+            .iter()
+            .find(|(f, _)| *f == field)
+            .map(|(_, m)| m.positions.as_slice())
+            .unwrap_or_default()
+    }
+}
+
+/// Run a parsed query: a single word is ranked fuzzily, structured queries
+/// filter and come back sorted by service
+pub fn find<'a>(vault: &'a CredentialVault, query: &Query, exact: bool) -> Vec<SearchHit<'a>> {
+    if let Some(text) = query.as_plain_text() {
+        return rank(vault, text, exact);
+    }
+
+    let now = Utc::now();
+    let mut hits: Vec<SearchHit> = vault
+        .credentials
+        .iter()
+        .filter(|c| query.matches(c, now))
+        .map(|credential| SearchHit {
+            credential,
+            score: 0,
+            matches: Vec::new(),
+        })
+        .collect();
+    hits.sort_by_key(|hit| hit.credential.service.to_lowercase());
+    hits
+}
+
+/// Search the vault, best hits first. `exact` restricts matching to substrings.
+pub fn rank<'a>(vault: &'a CredentialVault, query: &str, exact: bool) -> Vec<SearchHit<'a>> {
+    let matcher = if exact {
+        fuzzy::exact_match
+    } else {
+        fuzzy::fuzzy_match
+    };
+
+    let mut hits: Vec<SearchHit> = vault
+        .credentials
+        .iter()
+        .filter_map(|credential| {
+            let mut fields = vec![
+                (SearchField::Service, Some(credential.service.as_str())),
+                (SearchField::Username, credential.username.as_deref()),
+                (SearchField::Url, credential.url.as_deref()),
+                (SearchField::Folder, credential.folder.as_deref()),
+            ];
+            fields.extend(
+                credential
+                    .tags
+                    .iter()
+                    .enumerate()
+                    .map(|(i, tag)| (SearchField::Tag(i), Some(tag.as_str()))),
+            );
+
+            let matches: Vec<(SearchField, Match)> = fields
+                .into_iter()
+                .filter_map(|(field, text)| Some((field, matcher(query, text?)?)))
+                .collect();
+
+            let score = matches
+                .iter()
+                .map(|(field, m)| m.score * field.weight())
+                .max();
+
+            match score {
+                Some(score) => Some(SearchHit {
+                    credential,
+                    score,
+                    matches,
+                }),
+                // Custom fields and item details are only searched by substring
+                None if credential.matches_search(query) => Some(SearchHit {
+                    credential,
+                    score: 0,
+                    matches,
+                }),
+                None => None,
+            }
+        })
+        .collect();
+
+    hits.sort_by(|a, b| {
+        b.score
+            .cmp(&a.score)
+            .then_with(|| a.credential.service.cmp(&b.credential.service))
+    });
+    hits
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_service_matches_outrank_other_fields() {
+        let mut vault = CredentialVault::new();
+        let mut mail = Credential::new("Mail".into(), "pw".into());
+        mail.username = Some("github-alerts".into());
+        vault.add_credentail(mail);
+        vault.add_credentail(Credential::new("GitHub".into(), "pw".into()));
+        let mut bank = Credential::new("Bank".into(), "pw".into());
+        bank.set_field("note".into(), "github sponsor".into(), false);
+        vault.add_credentail(bank);
+
+        let services: Vec<_> = rank(&vault, "gitub", false)
+            .iter()
+            .map(|hit| hit.credential.service.as_str())
+            .collect();
+        assert_eq!(services, vec!["GitHub", "Mail"]);
+
+        // Exact mode finds nothing for the typo but still sees custom fields
+        assert!(rank(&vault, "gitub", true).is_empty());
+        let exact: Vec<_> = rank(&vault, "github", true)
+            .iter()
+            .map(|hit| hit.credential.service.as_str())
+            .collect();
+        assert_eq!(exact, vec!["GitHub", "Mail", "Bank"]);
+    }
+
+    #[test]
+    fn test_unrelated_entries_are_left_out() {
+        let mut vault = CredentialVault::new();
+        vault.add_credentail(Credential::new("PayPal".into(), "pw".into()));
+        vault.add_credentail(Credential::new("Spotify Family".into(), "pw".into()));
+
+        let services: Vec<_> = rank(&vault, "pay", false)
+            .iter()
+            .map(|hit| hit.credential.service.as_str())
+            .collect();
+        assert_eq!(services, vec!["PayPal"]);
+    }
+}
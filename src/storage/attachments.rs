@@ -0,0 +1,248 @@
+// Encrypted attachment blobs stored beside the vault file
+use crate::models::credential::{Attachment, Credential, CredentialVault};
+use crate::utils::Utc;
+use crate::utils::crypto::{Encryptor, MasterKey};
+use base64::{Engine as _, engine::general_purpose};
+use sha2::{Digest, Sha256};
+use std::fs;
+use std::path::{Path, PathBuf};
+use uuid::Uuid;
+
+pub const ATTACHMENTS_DIR: &str = "attachments";
+
+/// Largest file accepted as an attachment (10 MiB)
+pub const MAX_ATTACHMENT_SIZE: u64 = 10 * 1024 * 1024;
+
+/// Directory of blobs, one per attachment, each under its own random key
+pub struct AttachmentStore {
+    dir: PathBuf,
+}
+
+impl AttachmentStore {
+    pub fn new(vault_dir: &Path) -> Self {
+        Self {
+            dir: vault_dir.join(ATTACHMENTS_DIR),
+        }
+    }
+
+    /// Encrypt `data` under a fresh key and write it as a new blob
+    pub fn store(&self, name: String, data: &[u8]) -> anyhow::Result<Attachment> {
+        if data.len() as u64 > MAX_ATTACHMENT_SIZE {
+            return Err(anyhow::anyhow!(
+                "❌ Attachment is {} bytes, the limit is {} bytes",
+                data.len(),
+                MAX_ATTACHMENT_SIZE
+            ));
+        }
+
+        let key = MasterKey::generate();
+        let encrypted = Encryptor::encrypt(&key, data)?;
+
+        let attachment = Attachment {
+            id: Uuid::new_v4(),
+            name,
+            size: data.len() as u64,
+            sha256: hex::encode(Sha256::digest(data)),
+            key: general_purpose::STANDARD.encode(key.as_bytes()),
+            created_at: Utc::now(),
+        };
+
+        self.ensure_dir()?;
+        let path = self.blob_path(&attachment.id);
+        let temp_path = path.with_extension("tmp");
+        fs::write(&temp_path, encrypted)?;
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mut perms = fs::metadata(&temp_path)?.permissions();
+            perms.set_mode(0o600);
+            fs::set_permissions(&temp_path, perms)?;
+        }
+
+        fs::rename(&temp_path, &path)?;
+
+        Ok(attachment)
+    }
+
+    /// Read and decrypt a blob, verifying it is the file that was attached
+    pub fn load(&self, attachment: &Attachment) -> anyhow::Result<Vec<u8>> {
+        let path = self.blob_path(&attachment.id);
+        let encrypted = fs::read(&path).map_err(|e| {
+            anyhow::anyhow!("❌ Attachment blob {} is missing: {}", path.display(), e)
+        })?;
+
+        let key_bytes = general_purpose::STANDARD
+            .decode(&attachment.key)
+            .map_err(|e| anyhow::anyhow!("Attachment key decode error: {}", e))?;
+        let key = MasterKey::from_bytes(&key_bytes)?;
+
+        // GCM authenticates the blob; the hash ties it to this attachment entry
+        let data = Encryptor::decrypt(&encrypted, &key)
+            .map_err(|_| anyhow::anyhow!("❌ Attachment '{}' is corrupted", attachment.name))?;
+        if data.len() as u64 != attachment.size
+            || hex::encode(Sha256::digest(&data)) != attachment.sha256
+        {
+            return Err(anyhow::anyhow!(
+                "❌ Attachment '{}' failed its integrity check",
+                attachment.name
+            ));
+        }
+
+        Ok(data)
+    }
+
+    /// Delete a blob; a blob that is already gone is not an error
+    pub fn remove(&self, attachment: &Attachment) -> anyhow::Result<()> {
+        let path = self.blob_path(&attachment.id);
+        if path.exists() {
+            fs::remove_file(path)?;
+        }
+        Ok(())
+    }
+
+    /// Attachments in `vault` whose blob is not in this directory, e.g. after
+    /// a WebDAV or S3 sync, which only carries the vault file
+    pub fn missing<'a>(&self, vault: &'a CredentialVault) -> Vec<(&'a Credential, &'a Attachment)> {
+        vault
+            .credentials
+            .iter()
+            .flat_map(|c| c.attachments.iter().map(move |a| (c, a)))
+            .filter(|(_, a)| !self.blob_path(&a.id).exists())
+            .collect()
+    }
+
+    /// Copy the blob of `attachment` from another vault's store. Returns false
+    /// when neither store has it.
+    pub fn copy_from(
+        &self,
+        other: &AttachmentStore,
+        attachment: &Attachment,
+    ) -> anyhow::Result<bool> {
+        let path = self.blob_path(&attachment.id);
+        if path.exists() {
+            return Ok(true);
+        }
+        let source = other.blob_path(&attachment.id);
+        if !source.exists() {
+            return Ok(false);
+        }
+
+        // Blobs are encrypted under the key in the vault entry, so they copy as-is
+        self.ensure_dir()?;
+        let temp_path = path.with_extension("tmp");
+        fs::copy(&source, &temp_path)?;
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mut perms = fs::metadata(&temp_path)?.permissions();
+            perms.set_mode(0o600);
+            fs::set_permissions(&temp_path, perms)?;
+        }
+
+        fs::rename(&temp_path, &path)?;
+        Ok(true)
+    }
+
+    fn blob_path(&self, id: &Uuid) -> PathBuf {
+        self.dir.join(format!("{}.blob", id))
+    }
+
+    fn ensure_dir(&self) -> anyhow::Result<()> {
+        if !self.dir.exists() {
+            fs::create_dir_all(&self.dir)?;
+
+            #[cfg(unix)]
+            {
+                use std::os::unix::fs::PermissionsExt;
+                let mut perms = fs::metadata(&self.dir)?.permissions();
+                perms.set_mode(0o700);
+                fs::set_permissions(&self.dir, perms)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_store() -> (AttachmentStore, PathBuf) {
+        let dir = std::env::temp_dir().join(format!("pwdmgr-attach-{}", Uuid::new_v4()));
+        (AttachmentStore::new(&dir), dir)
+    }
+
+    #[test]
+    fn test_round_trip_and_tamper_detection() {
+        let (store, dir) = temp_store();
+        let attachment = store.store("codes.txt".into(), b"recovery codes").unwrap();
+        assert_eq!(store.load(&attachment).unwrap(), b"recovery codes");
+
+        // Flipping a ciphertext byte must be caught by GCM
+        let path = store.blob_path(&attachment.id);
+        let mut blob = fs::read(&path).unwrap();
+        let last = blob.len() - 1;
+        blob[last] ^= 1;
+        fs::write(&path, blob).unwrap();
+        assert!(store.load(&attachment).is_err());
+
+        // A vault entry pointing at a different file is caught by the hash
+        let other = store.store("other.txt".into(), b"something else").unwrap();
+        let swapped = Attachment {
+            id: other.id,
+            key: other.key.clone(),
+            ..attachment.clone()
+        };
+        assert!(store.load(&swapped).is_err());
+
+        store.remove(&attachment).unwrap();
+        assert!(!path.exists());
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_missing_blobs_and_copy_from() {
+        let (store, dir) = temp_store();
+        let (other, other_dir) = temp_store();
+        let local = store.store("local.txt".into(), b"here").unwrap();
+        let remote = other.store("remote.txt".into(), b"over there").unwrap();
+        let lost = Attachment {
+            id: Uuid::new_v4(),
+            ..remote.clone()
+        };
+
+        let mut vault = CredentialVault::new();
+        let mut credential = Credential::new("Bank".into(), "secret".into());
+        credential.attachments = vec![local, remote.clone(), lost.clone()];
+        vault.credentials.push(credential);
+
+        let missing: Vec<_> = store
+            .missing(&vault)
+            .into_iter()
+            .map(|(_, a)| a.id)
+            .collect();
+        assert_eq!(missing, vec![remote.id, lost.id]);
+
+        assert!(store.copy_from(&other, &remote).unwrap());
+        assert_eq!(store.load(&remote).unwrap(), b"over there");
+        assert!(!store.copy_from(&other, &lost).unwrap());
+        let missing: Vec<_> = store
+            .missing(&vault)
+            .into_iter()
+            .map(|(_, a)| a.id)
+            .collect();
+        assert_eq!(missing, vec![lost.id]);
+
+        fs::remove_dir_all(dir).unwrap();
+        fs::remove_dir_all(other_dir).unwrap();
+    }
+
+    #[test]
+    fn test_size_limit() {
+        let (store, _dir) = temp_store();
+        let data = vec![0u8; MAX_ATTACHMENT_SIZE as usize + 1];
+        assert!(store.store("big.bin".into(), &data).is_err());
+    }
+}
@@ -0,0 +1,145 @@
+// Per-machine settings stored next to the vault (never synced)
+use crate::utils::clipboard::BackendChoice;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+pub const CONFIG_FILE_NAME: &str = "config.json";
+
+/// User settings for this vault directory
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct Config {
+    /// Named remote sync targets, e.g. "default", "work"
+    #[serde(default)]
+    pub sync_profiles: BTreeMap<String, SyncProfile>,
+    /// Seconds before a copied secret is cleared from the clipboard (0 = never)
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub clipboard_clear_after: Option<u64>,
+    /// auto, native, osc52 or command
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub clipboard_backend: Option<BackendChoice>,
+    /// Command that reads the text to copy from stdin, e.g. "wl-copy"
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub clipboard_command: Option<String>,
+    /// Command that prints the clipboard, e.g. "wl-paste --no-newline"
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub clipboard_paste_command: Option<String>,
+    /// Local Have I Been Pwned SHA-1 file checked by `breach-check` and `add`
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub hibp_file: Option<PathBuf>,
+}
+
+/// Where a profile syncs the encrypted vault blob to
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(tag = "provider", rename_all = "lowercase")]
+pub enum SyncProfile {
+    Webdav {
+        /// Full URL of the remote vault file
+        url: String,
+        username: Option<String>,
+        /// Plaintext password from older versions, moved into the vault on the next sync
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        password: Option<String>,
+    },
+    S3 {
+        /// Endpoint such as https://s3.eu-west-1.amazonaws.com or http://localhost:9000
+        endpoint: String,
+        region: String,
+        bucket: String,
+        /// Object key of the vault blob
+        key: String,
+        access_key_id: String,
+        /// Plaintext secret from older versions, moved into the vault on the next sync
+        #[serde(default, skip_serializing_if = "Option::is_none")]
+        secret_access_key: Option<String>,
+    },
+}
+
+impl SyncProfile {
+    /// Whether the remote needs a password or secret key from the vault
+    pub fn needs_secret(&self) -> bool {
+        match self {
+            SyncProfile::Webdav { username, .. } => username.is_some(),
+            SyncProfile::S3 { .. } => true,
+        }
+    }
+
+    /// Remove a secret that an older version left in config.json
+    pub fn take_legacy_secret(&mut self) -> Option<String> {
+        match self {
+            SyncProfile::Webdav { password, .. } => password.take(),
+            SyncProfile::S3 {
+                secret_access_key, ..
+            } => secret_access_key.take(),
+        }
+    }
+}
+
+impl Config {
+    /// Load config from the vault directory, defaulting when none exists yet
+    pub fn load(vault_dir: &Path) -> anyhow::Result<Self> {
+        let path = Self::path(vault_dir);
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+
+        let data = fs::read(&path)?;
+        serde_json::from_slice(&data)
+            .map_err(|e| anyhow::anyhow!("Invalid config at {}: {}", path.display(), e))
+    }
+
+    /// Save config, readable by the owner only (older versions kept remote credentials here)
+    pub fn save(&self, vault_dir: &Path) -> anyhow::Result<()> {
+        let path = Self::path(vault_dir);
+        fs::write(&path, serde_json::to_string_pretty(self)?)?;
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mut perms = fs::metadata(&path)?.permissions();
+            perms.set_mode(0o600);
+            fs::set_permissions(&path, perms)?;
+        }
+
+        Ok(())
+    }
+
+    pub fn path(vault_dir: &Path) -> PathBuf {
+        vault_dir.join(CONFIG_FILE_NAME)
+    }
+}
+
+pub const SYNC_STATE_FILE_NAME: &str = "sync_state.json";
+
+/// What this machine last agreed on with each sync profile
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct SyncState {
+    #[serde(default)]
+    pub profiles: BTreeMap<String, ProfileState>,
+}
+
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+pub struct ProfileState {
+    /// ETag of the remote blob after the last successful sync
+    pub etag: Option<String>,
+    /// SHA-256 of the local vault file after the last successful sync
+    pub local_hash: Option<String>,
+}
+
+impl SyncState {
+    pub fn load(vault_dir: &Path) -> anyhow::Result<Self> {
+        let path = vault_dir.join(SYNC_STATE_FILE_NAME);
+        if !path.exists() {
+            return Ok(Self::default());
+        }
+
+        Ok(serde_json::from_slice(&fs::read(path)?)?)
+    }
+
+    pub fn save(&self, vault_dir: &Path) -> anyhow::Result<()> {
+        let path = vault_dir.join(SYNC_STATE_FILE_NAME);
+        fs::write(path, serde_json::to_string_pretty(self)?)?;
+        Ok(())
+    }
+}
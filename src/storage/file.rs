@@ -1,4 +1,6 @@
 use crate::models::credential::CredentialVault;
+use crate::storage::attachments::AttachmentStore;
+use crate::storage::git::GitRepo;
 use crate::utils::crypto::{Encryptor, MasterKey, Passwordhasher};
 use serde::{Deserialize, Serialize};
 use std::fs;
@@ -54,6 +56,11 @@ impl FileStorage {
         Ok(Self { vault_path })
     }
 
+    /// Open a vault file at an arbitrary path (e.g. a copy from another machine)
+    pub fn from_path(vault_path: PathBuf) -> Self {
+        Self { vault_path }
+    }
+
     /// check if vault file exists
     pub fn vault_exists(&self) -> bool {
         self.vault_path.exists()
@@ -79,7 +86,7 @@ impl FileStorage {
         let salt_byte = general_purpose::STANDARD
             .decode({
                 let mut padded = salt_string.clone();
-                while padded.len() % 4 != 0 {
+                while !padded.len().is_multiple_of(4) {
                     padded.push('=');
                 }
                 padded
@@ -110,7 +117,17 @@ impl FileStorage {
     /// Load and decrypt vault with master password
     pub fn load_vault(&self, master_password: &str) -> anyhow::Result<CredentialVault> {
         let vault_file = self.load_vault_file()?;
+        let vault = Self::decrypt_vault_file(&vault_file, master_password)?;
 
+        println!("🔓 Vault unlocked Successfully!");
+        Ok(vault)
+    }
+
+    /// Decrypt an already loaded vault file (e.g. one read from git history)
+    pub fn decrypt_vault_file(
+        vault_file: &VaultFile,
+        master_password: &str,
+    ) -> anyhow::Result<CredentialVault> {
         // verify master password
         if !Passwordhasher::verify_password(master_password, &vault_file.password_hash)? {
             return Err(anyhow::anyhow!("❌ Invalid master password"));
@@ -120,7 +137,7 @@ impl FileStorage {
         let salt_bytes = general_purpose::STANDARD
             .decode({
                 let mut padded = vault_file.salt.clone();
-                while padded.len() % 4 != 0 {
+                while !padded.len().is_multiple_of(4) {
                     padded.push('=');
                 }
                 padded
@@ -133,12 +150,26 @@ impl FileStorage {
         let vault_json = String::from_utf8(decrypted_data)?;
         let vault: CredentialVault = serde_json::from_str(&vault_json)?;
 
-        println!("🔓 Vault unlocked Successfully!");
         Ok(vault)
     }
 
     /// Save encrypted vault to disk
     pub fn save_vault(&self, vault: &CredentialVault, master_password: &str) -> anyhow::Result<()> {
+        self.write_vault(vault, master_password)?;
+
+        println!("💾 Vault saved successfullt!");
+
+        self.commit_if_tracked("Update vault");
+
+        Ok(())
+    }
+
+    /// Re-encrypt vault and write it to disk without recording a git commit
+    pub fn write_vault(
+        &self,
+        vault: &CredentialVault,
+        master_password: &str,
+    ) -> anyhow::Result<()> {
         let mut vault_file = self.load_vault_file()?;
 
         // verify master password
@@ -150,7 +181,7 @@ impl FileStorage {
         let salt_bytes = general_purpose::STANDARD
             .decode({
                 let mut padded = vault_file.salt.clone();
-                while padded.len() % 4 != 0 {
+                while !padded.len().is_multiple_of(4) {
                     padded.push('=');
                 }
                 padded
@@ -166,8 +197,6 @@ impl FileStorage {
 
         self.save_vault_file(&vault_file)?;
 
-        println!("💾 Vault saved successfullt!");
-
         Ok(())
     }
 
@@ -187,7 +216,7 @@ impl FileStorage {
         let new_salt_bytes = general_purpose::STANDARD
             .decode({
                 let mut padded = new_salt_string.clone();
-                while padded.len() % 4 != 0 {
+                while !padded.len().is_multiple_of(4) {
                     padded.push('=');
                 }
                 padded
@@ -210,6 +239,8 @@ impl FileStorage {
         self.save_vault_file(&vault_file)?;
         println!("🔑 Master password changed successfully!");
 
+        self.commit_if_tracked("Change master password");
+
         Ok(())
     }
 
@@ -218,8 +249,45 @@ impl FileStorage {
         &self.vault_path
     }
 
-    /// Internal: Load raw vault file from disk
-    fn load_vault_file(&self) -> anyhow::Result<VaultFile> {
+    /// Directory holding the vault file (and its git repository, if any)
+    pub fn vault_dir(&self) -> &Path {
+        self.vault_path.parent().unwrap_or_else(|| Path::new("."))
+    }
+
+    /// File name of the vault inside its directory
+    pub fn vault_file_name(&self) -> String {
+        format!("vault.{}", VAULT_FILE_EXTENSION)
+    }
+
+    /// Replace the vault with an already encrypted vault file (e.g. downloaded from a remote)
+    pub fn replace_vault_file(&self, vault_file: &VaultFile, reason: &str) -> anyhow::Result<()> {
+        self.save_vault_file(vault_file)?;
+        self.commit_if_tracked(reason);
+        Ok(())
+    }
+
+    /// Encrypted attachment blobs kept beside the vault file
+    pub fn attachments(&self) -> AttachmentStore {
+        AttachmentStore::new(self.vault_dir())
+    }
+
+    /// Git repository wrapping the vault directory, if one has been initialized
+    pub fn git_repo(&self) -> Option<GitRepo> {
+        GitRepo::open(self.vault_dir())
+    }
+
+    /// Record a commit after a successful save when the vault is under git.
+    /// The save itself already succeeded, so failures are only reported.
+    pub fn commit_if_tracked(&self, message: &str) {
+        if let Some(repo) = self.git_repo()
+            && let Err(e) = repo.commit_all(message)
+        {
+            println!("⚠️ Vault saved but git commit failed: {}", e);
+        }
+    }
+
+    /// Load raw (still encrypted) vault file from disk
+    pub fn load_vault_file(&self) -> anyhow::Result<VaultFile> {
         if !self.vault_exists() {
             return Err(anyhow::anyhow!(
                 "Vault not found at {}. Run 'init' to create one",
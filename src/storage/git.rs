@@ -0,0 +1,474 @@
+// Thin wrapper over the `git` binary for versioning the vault directory
+use crate::storage::config::{CONFIG_FILE_NAME, SYNC_STATE_FILE_NAME};
+use std::path::{Path, PathBuf};
+use std::process::{Command, Output};
+
+const DEFAULT_REMOTE: &str = "origin";
+
+/// Files that stay on this machine: settings (which may hold remote
+/// credentials) and sync bookkeeping
+const LOCAL_FILES: [&str; 2] = [CONFIG_FILE_NAME, SYNC_STATE_FILE_NAME];
+
+/// Identity used for commits when the user has not configured one
+const FALLBACK_NAME: &str = "pwdmgr";
+const FALLBACK_EMAIL: &str = "pwdmgr@localhost";
+
+/// Git repository rooted at the vault directory
+pub struct GitRepo {
+    dir: PathBuf,
+}
+
+/// How the local branch relates to its remote counterpart after a fetch
+#[derive(Debug, PartialEq, Eq)]
+pub enum Divergence {
+    /// Remote has nothing we don't already have
+    UpToDate,
+    /// Local branch can simply be moved forward
+    FastForward,
+    /// Both sides have commits the other lacks
+    Diverged,
+    /// Remote branch does not exist yet
+    NoUpstream,
+}
+
+impl GitRepo {
+    /// Open an existing repository, returns None if `dir` is not under git
+    pub fn open(dir: &Path) -> Option<Self> {
+        if dir.join(".git").exists() {
+            Some(Self {
+                dir: dir.to_path_buf(),
+            })
+        } else {
+            None
+        }
+    }
+
+    /// Initialize a new repository in `dir`
+    pub fn init(dir: &Path) -> anyhow::Result<Self> {
+        let repo = Self {
+            dir: dir.to_path_buf(),
+        };
+        repo.git(&["init", "--quiet"])?;
+        repo.ensure_ignored()?;
+
+        Ok(repo)
+    }
+
+    /// Point the default remote at `url` (adding or replacing it)
+    pub fn set_remote(&self, url: &str) -> anyhow::Result<()> {
+        if self.remote_url().is_some() {
+            self.git(&["remote", "set-url", DEFAULT_REMOTE, url])?;
+        } else {
+            self.git(&["remote", "add", DEFAULT_REMOTE, url])?;
+        }
+        Ok(())
+    }
+
+    /// URL of the default remote, if configured
+    pub fn remote_url(&self) -> Option<String> {
+        self.git(&["remote", "get-url", DEFAULT_REMOTE]).ok()
+    }
+
+    /// Stage everything and commit. Returns false when there was nothing to commit.
+    pub fn commit_all(&self, message: &str) -> anyhow::Result<bool> {
+        self.ensure_ignored()?;
+        self.git(&["add", "-A"])?;
+
+        // An in-progress merge must be concluded even if the tree matches HEAD
+        let merging = self.dir.join(".git").join("MERGE_HEAD").exists();
+        if !merging && !self.has_staged_changes()? {
+            return Ok(false);
+        }
+
+        self.commit(message)?;
+        Ok(true)
+    }
+
+    /// Push the current branch and set its upstream
+    pub fn push(&self) -> anyhow::Result<()> {
+        let branch = self.current_branch()?;
+        self.git(&["push", "--quiet", "-u", DEFAULT_REMOTE, &branch])?;
+        Ok(())
+    }
+
+    /// Fetch the remote and work out how the local branch relates to it
+    pub fn fetch(&self) -> anyhow::Result<Divergence> {
+        self.git(&["fetch", "--quiet", DEFAULT_REMOTE])?;
+
+        let upstream = self.upstream_ref()?;
+        if self
+            .git(&["rev-parse", "--verify", "--quiet", &upstream])
+            .is_err()
+        {
+            return Ok(Divergence::NoUpstream);
+        }
+
+        if self.is_ancestor(&upstream, "HEAD")? {
+            Ok(Divergence::UpToDate)
+        } else if self.is_ancestor("HEAD", &upstream)? {
+            Ok(Divergence::FastForward)
+        } else {
+            Ok(Divergence::Diverged)
+        }
+    }
+
+    /// Remote tracking ref for the current branch (e.g. `origin/master`)
+    pub fn upstream_ref(&self) -> anyhow::Result<String> {
+        Ok(format!("{}/{}", DEFAULT_REMOTE, self.current_branch()?))
+    }
+
+    /// Move the local branch forward to `rev`
+    pub fn fast_forward(&self, rev: &str) -> anyhow::Result<()> {
+        self.git(&["merge", "--quiet", "--ff-only", rev])?;
+        Ok(())
+    }
+
+    /// Common ancestor of two revisions, if the histories share one
+    pub fn merge_base(&self, a: &str, b: &str) -> Option<String> {
+        self.git(&["merge-base", a, b]).ok()
+    }
+
+    /// Start a merge of `rev` without committing. Files changed on both sides
+    /// keep our version so the caller can write the real result before committing.
+    pub fn begin_merge(&self, rev: &str) -> anyhow::Result<()> {
+        self.git_as_user(&[
+            "merge",
+            "--quiet",
+            "--no-ff",
+            "--no-commit",
+            "-X",
+            "ours",
+            rev,
+        ])?;
+        Ok(())
+    }
+
+    /// Throw away an in-progress merge
+    pub fn abort_merge(&self) -> anyhow::Result<()> {
+        self.git(&["merge", "--abort"])?;
+        Ok(())
+    }
+
+    /// Raw contents of `path` at revision `rev`
+    pub fn show_file(&self, rev: &str, path: &str) -> anyhow::Result<Vec<u8>> {
+        let output = self.output(&["show", &format!("{}:{}", rev, path)])?;
+        Ok(output.stdout)
+    }
+
+    /// One-line history entries, newest first
+    pub fn log(&self, limit: usize) -> anyhow::Result<Vec<String>> {
+        let limit = limit.to_string();
+        let log = self.git(&[
+            "log",
+            "-n",
+            &limit,
+            "--date=format:%Y-%m-%d %H:%M",
+            "--format=%h  %ad  %s",
+        ])?;
+        Ok(log.lines().map(str::to_string).collect())
+    }
+
+    /// Never version half-written temp files or per-machine files. Repositories
+    /// created by older versions may lack the ignore entries or already track
+    /// the files, so this runs before every commit.
+    fn ensure_ignored(&self) -> anyhow::Result<()> {
+        let path = self.dir.join(".gitignore");
+        let mut ignore = match std::fs::read_to_string(&path) {
+            Ok(ignore) => ignore,
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => String::new(),
+            Err(e) => return Err(e.into()),
+        };
+
+        let missing: Vec<&str> = std::iter::once("*.tmp")
+            .chain(LOCAL_FILES)
+            .filter(|entry| !ignore.lines().any(|line| line.trim() == *entry))
+            .collect();
+        if !missing.is_empty() {
+            if !ignore.is_empty() && !ignore.ends_with('\n') {
+                ignore.push('\n');
+            }
+            for entry in missing {
+                ignore.push_str(entry);
+                ignore.push('\n');
+            }
+            std::fs::write(&path, ignore)?;
+        }
+
+        // Untrack them but leave the files on disk
+        let mut args = vec!["rm", "--cached", "--quiet", "--ignore-unmatch", "--"];
+        args.extend(LOCAL_FILES);
+        self.git(&args)?;
+        Ok(())
+    }
+
+    fn current_branch(&self) -> anyhow::Result<String> {
+        self.git(&["symbolic-ref", "--short", "HEAD"])
+    }
+
+    fn is_ancestor(&self, ancestor: &str, descendant: &str) -> anyhow::Result<bool> {
+        let output = self.command(&["merge-base", "--is-ancestor", ancestor, descendant])?;
+        Ok(output.status.success())
+    }
+
+    fn has_staged_changes(&self) -> anyhow::Result<bool> {
+        // exit code 1 means there are differences
+        let output = self.command(&["diff", "--cached", "--quiet"])?;
+        Ok(!output.status.success())
+    }
+
+    fn commit(&self, message: &str) -> anyhow::Result<()> {
+        self.git_as_user(&["commit", "--quiet", "-m", message])?;
+        Ok(())
+    }
+
+    /// Run a git command that records an author, falling back to a
+    /// placeholder identity when the user has not configured one
+    fn git_as_user(&self, args: &[&str]) -> anyhow::Result<String> {
+        let name = format!("user.name={}", FALLBACK_NAME);
+        let email = format!("user.email={}", FALLBACK_EMAIL);
+
+        let mut full_args: Vec<&str> = Vec::new();
+        if self.git(&["config", "user.email"]).is_err() {
+            full_args.extend(["-c", &name, "-c", &email]);
+        }
+        full_args.extend(args);
+
+        self.git(&full_args)
+    }
+
+    /// Run git and return trimmed stdout, failing on non-zero exit
+    fn git(&self, args: &[&str]) -> anyhow::Result<String> {
+        let output = self.output(args)?;
+        Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+    }
+
+    fn output(&self, args: &[&str]) -> anyhow::Result<Output> {
+        let output = self.command(args)?;
+
+        if !output.status.success() {
+            return Err(anyhow::anyhow!(
+                "git {} failed: {}",
+                args.first().unwrap_or(&""),
+                String::from_utf8_lossy(&output.stderr).trim()
+            ));
+        }
+
+        Ok(output)
+    }
+
+    fn command(&self, args: &[&str]) -> anyhow::Result<Output> {
+        Command::new("git")
+            .arg("-C")
+            .arg(&self.dir)
+            .args(args)
+            .output()
+            .map_err(|e| anyhow::anyhow!("Could not run git: {}", e))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir =
+            std::env::temp_dir().join(format!("pwdmgr-git-{}-{}", name, uuid::Uuid::new_v4()));
+        fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    fn bare_remote() -> PathBuf {
+        let remote = temp_dir("remote");
+        let status = Command::new("git")
+            .args(["init", "--quiet", "--bare"])
+            .arg(&remote)
+            .status()
+            .unwrap();
+        assert!(status.success());
+        remote
+    }
+
+    /// A bare remote plus one clone that has pushed a first vault commit
+    fn remote_with_clone() -> (PathBuf, GitRepo) {
+        let remote = bare_remote();
+
+        let dir = temp_dir("ours");
+        let repo = GitRepo::init(&dir).unwrap();
+        repo.set_remote(remote.to_str().unwrap()).unwrap();
+        fs::write(dir.join("vault.vault"), "v1").unwrap();
+        repo.commit_all("Initial vault commit").unwrap();
+        repo.push().unwrap();
+        (remote, repo)
+    }
+
+    fn clone(remote: &Path) -> GitRepo {
+        let dir = temp_dir("theirs");
+        let status = Command::new("git")
+            .args(["clone", "--quiet"])
+            .arg(remote)
+            .arg(&dir)
+            .status()
+            .unwrap();
+        assert!(status.success());
+        GitRepo::open(&dir).unwrap()
+    }
+
+    fn write_and_commit(repo: &GitRepo, file: &str, contents: &str) {
+        fs::write(repo.dir.join(file), contents).unwrap();
+        assert!(repo.commit_all("Update vault").unwrap());
+    }
+
+    #[test]
+    fn test_init_and_commit_all() {
+        let dir = temp_dir("init");
+        assert!(GitRepo::open(&dir).is_none());
+        let repo = GitRepo::init(&dir).unwrap();
+        assert!(GitRepo::open(&dir).is_some());
+
+        fs::write(dir.join("vault.vault"), "v1").unwrap();
+        fs::write(dir.join("vault.tmp"), "half written").unwrap();
+        assert!(repo.commit_all("Initial vault commit").unwrap());
+        // Nothing changed since, so there is nothing to record
+        assert!(!repo.commit_all("Update vault").unwrap());
+
+        let log = repo.log(10).unwrap();
+        assert_eq!(log.len(), 1);
+        assert!(log[0].ends_with("Initial vault commit"));
+        let files = repo.git(&["ls-files"]).unwrap();
+        assert_eq!(
+            files.lines().collect::<Vec<_>>(),
+            [".gitignore", "vault.vault"]
+        );
+        assert_eq!(repo.show_file("HEAD", "vault.vault").unwrap(), b"v1");
+
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_local_files_never_committed() {
+        let history = |repo: &GitRepo| repo.git(&["log", "--all", "--format=", "--name-only"]);
+
+        // A repository set up before the ignore entries existed
+        let dir = temp_dir("legacy");
+        let repo = GitRepo { dir: dir.clone() };
+        repo.git(&["init", "--quiet"]).unwrap();
+        fs::write(dir.join("vault.vault"), "v1").unwrap();
+        fs::write(
+            dir.join(CONFIG_FILE_NAME),
+            r#"{"secret_access_key": "s3cr3t"}"#,
+        )
+        .unwrap();
+        fs::write(dir.join(SYNC_STATE_FILE_NAME), "{}").unwrap();
+        assert!(repo.commit_all("Initial vault commit").unwrap());
+        // Editing the config alone leaves nothing to commit
+        let changed = r#"{"secret_access_key": "changed"}"#;
+        fs::write(dir.join(CONFIG_FILE_NAME), changed).unwrap();
+        assert!(!repo.commit_all("Update vault").unwrap());
+
+        let committed = history(&repo).unwrap();
+        assert!(committed.contains("vault.vault"));
+        assert!(!committed.contains(CONFIG_FILE_NAME));
+        assert!(!committed.contains(SYNC_STATE_FILE_NAME));
+        let ignore = fs::read_to_string(dir.join(".gitignore")).unwrap();
+        assert_eq!(ignore, "*.tmp\nconfig.json\nsync_state.json\n");
+        fs::remove_dir_all(dir).unwrap();
+
+        // A repository that already committed the config
+        let dir = temp_dir("tracked");
+        let repo = GitRepo { dir: dir.clone() };
+        repo.git(&["init", "--quiet"]).unwrap();
+        fs::write(dir.join(".gitignore"), "*.tmp").unwrap();
+        fs::write(dir.join(CONFIG_FILE_NAME), "{}").unwrap();
+        repo.git(&["add", "-A"]).unwrap();
+        repo.commit("Old commit").unwrap();
+
+        write_and_commit(&repo, "vault.vault", "v2");
+        let tree = repo.git(&["ls-tree", "-r", "--name-only", "HEAD"]).unwrap();
+        assert_eq!(
+            tree.lines().collect::<Vec<_>>(),
+            [".gitignore", "vault.vault"]
+        );
+        assert!(dir.join(CONFIG_FILE_NAME).exists());
+        let ignore = fs::read_to_string(dir.join(".gitignore")).unwrap();
+        assert_eq!(ignore, "*.tmp\nconfig.json\nsync_state.json\n");
+        fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[test]
+    fn test_push_pull_and_diverged_merge() {
+        let (remote, ours) = remote_with_clone();
+        let theirs = clone(&remote);
+        assert_eq!(theirs.fetch().unwrap(), Divergence::UpToDate);
+
+        // Only our side moved on: the other clone can fast-forward
+        write_and_commit(&ours, "vault.vault", "v2");
+        ours.push().unwrap();
+        assert_eq!(theirs.fetch().unwrap(), Divergence::FastForward);
+        theirs
+            .fast_forward(&theirs.upstream_ref().unwrap())
+            .unwrap();
+        assert_eq!(
+            fs::read_to_string(theirs.dir.join("vault.vault")).unwrap(),
+            "v2"
+        );
+
+        // Both sides change the vault: the histories diverge
+        write_and_commit(&ours, "vault.vault", "v3 ours");
+        ours.push().unwrap();
+        write_and_commit(&theirs, "vault.vault", "v3 theirs");
+        assert_eq!(theirs.fetch().unwrap(), Divergence::Diverged);
+        let upstream = theirs.upstream_ref().unwrap();
+        let base = theirs.merge_base("HEAD", &upstream).unwrap();
+        assert_eq!(theirs.show_file(&base, "vault.vault").unwrap(), b"v2");
+
+        // An aborted merge leaves the local vault untouched
+        theirs.begin_merge(&upstream).unwrap();
+        theirs.abort_merge().unwrap();
+        assert_eq!(
+            fs::read_to_string(theirs.dir.join("vault.vault")).unwrap(),
+            "v3 theirs"
+        );
+        assert_eq!(theirs.fetch().unwrap(), Divergence::Diverged);
+
+        // Changed on both sides keeps the local file until the caller writes the result
+        theirs.begin_merge(&upstream).unwrap();
+        assert_eq!(
+            fs::read_to_string(theirs.dir.join("vault.vault")).unwrap(),
+            "v3 theirs"
+        );
+        fs::write(theirs.dir.join("vault.vault"), "v4 merged").unwrap();
+        assert!(theirs.commit_all("Merge remote vault changes").unwrap());
+        assert!(!theirs.dir.join(".git").join("MERGE_HEAD").exists());
+        assert_eq!(theirs.fetch().unwrap(), Divergence::UpToDate);
+        assert!(theirs.is_ancestor(&upstream, "HEAD").unwrap());
+
+        // The merge pushes cleanly and reaches the first clone as a fast-forward
+        theirs.push().unwrap();
+        assert_eq!(ours.fetch().unwrap(), Divergence::FastForward);
+        ours.fast_forward(&ours.upstream_ref().unwrap()).unwrap();
+        assert_eq!(
+            fs::read_to_string(ours.dir.join("vault.vault")).unwrap(),
+            "v4 merged"
+        );
+
+        for dir in [remote, ours.dir, theirs.dir] {
+            fs::remove_dir_all(dir).unwrap();
+        }
+    }
+
+    #[test]
+    fn test_fetch_without_upstream() {
+        let remote = bare_remote();
+        let dir = temp_dir("local");
+        let repo = GitRepo::init(&dir).unwrap();
+        repo.set_remote(remote.to_str().unwrap()).unwrap();
+        assert_eq!(repo.remote_url().as_deref(), remote.to_str());
+        write_and_commit(&repo, "vault.vault", "v1");
+
+        assert_eq!(repo.fetch().unwrap(), Divergence::NoUpstream);
+
+        fs::remove_dir_all(remote).unwrap();
+        fs::remove_dir_all(dir).unwrap();
+    }
+}
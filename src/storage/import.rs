@@ -0,0 +1,393 @@
+// Readers for other password managers' export files
+use crate::models::credential::Credential;
+use crate::models::folder;
+use crate::models::item::{CardDetails, IdentityDetails, ItemDetails};
+use serde::Deserialize;
+use std::path::Path;
+use std::str::FromStr;
+
+/// Export formats `pwdmgr import` understands
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImportFormat {
+    /// KeePassXC "Export to CSV" (Group, Title, Username, Password, URL, Notes, TOTP, ...)
+    KeepassCsv,
+    /// Bitwarden unencrypted JSON export
+    BitwardenJson,
+}
+
+impl FromStr for ImportFormat {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_lowercase().as_str() {
+            "keepass" | "keepassxc" | "keepass-csv" | "csv" => Ok(ImportFormat::KeepassCsv),
+            "bitwarden" | "bitwarden-json" | "json" => Ok(ImportFormat::BitwardenJson),
+            _ => Err(anyhow::anyhow!(
+                "Unknown import format '{}' (expected keepass-csv or bitwarden-json)",
+                s
+            )),
+        }
+    }
+}
+
+impl ImportFormat {
+    /// Guess the format from the file extension
+    pub fn detect(path: &Path) -> Option<Self> {
+        match path.extension()?.to_str()?.to_lowercase().as_str() {
+            "csv" => Some(ImportFormat::KeepassCsv),
+            "json" => Some(ImportFormat::BitwardenJson),
+            _ => None,
+        }
+    }
+}
+
+/// Result of parsing an export file
+#[derive(Debug, Default)]
+pub struct Imported {
+    pub credentials: Vec<Credential>,
+    /// Entries that were imported in a degraded form, or skipped
+    pub warnings: Vec<String>,
+}
+
+pub fn parse(format: ImportFormat, data: &str) -> anyhow::Result<Imported> {
+    match format {
+        ImportFormat::KeepassCsv => parse_keepass_csv(data),
+        ImportFormat::BitwardenJson => parse_bitwarden_json(data),
+    }
+}
+
+fn parse_keepass_csv(data: &str) -> anyhow::Result<Imported> {
+    let mut rows = parse_csv(data)?.into_iter();
+    let header = rows
+        .next()
+        .ok_or_else(|| anyhow::anyhow!("❌ CSV file is empty"))?;
+    let column = |name: &str| header.iter().position(|h| h.eq_ignore_ascii_case(name));
+
+    let title = column("Title").ok_or_else(|| anyhow::anyhow!("❌ CSV has no 'Title' column"))?;
+    let (group, username, password, url, notes, totp) = (
+        column("Group"),
+        column("Username"),
+        column("Password"),
+        column("URL"),
+        column("Notes"),
+        column("TOTP"),
+    );
+
+    let mut imported = Imported::default();
+    for (line, row) in rows.enumerate() {
+        let cell = |index: Option<usize>| {
+            index
+                .and_then(|i| row.get(i))
+                .filter(|v| !v.is_empty())
+                .cloned()
+        };
+
+        let Some(service) = cell(Some(title)) else {
+            imported
+                .warnings
+                .push(format!("Row {} has no title, skipped", line + 2));
+            continue;
+        };
+
+        let mut credential = Credential::new(service, cell(password).unwrap_or_default());
+        credential.username = cell(username);
+        credential.url = cell(url);
+        credential.notes = cell(notes);
+        // KeePassXC prefixes every group path with the database root group
+        credential.folder = match cell(group) {
+            Some(group) => folder::normalize(group.split_once('/').map_or("", |(_, rest)| rest))?,
+            None => None,
+        };
+        if let Some(totp) = cell(totp) {
+            credential.set_field("otp".to_string(), totp, true);
+        }
+
+        imported.credentials.push(credential);
+    }
+
+    Ok(imported)
+}
+
+#[derive(Deserialize)]
+struct BitwardenExport {
+    #[serde(default)]
+    encrypted: bool,
+    #[serde(default)]
+    folders: Vec<BitwardenFolder>,
+    #[serde(default)]
+    items: Vec<BitwardenItem>,
+}
+
+#[derive(Deserialize)]
+struct BitwardenFolder {
+    id: String,
+    name: String,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct BitwardenItem {
+    folder_id: Option<String>,
+    /// 1 login, 2 secure note, 3 card, 4 identity
+    #[serde(rename = "type")]
+    kind: u8,
+    name: String,
+    notes: Option<String>,
+    login: Option<BitwardenLogin>,
+    card: Option<BitwardenCard>,
+    identity: Option<BitwardenIdentity>,
+    #[serde(default)]
+    fields: Vec<BitwardenField>,
+}
+
+#[derive(Deserialize)]
+struct BitwardenLogin {
+    username: Option<String>,
+    password: Option<String>,
+    totp: Option<String>,
+    #[serde(default)]
+    uris: Vec<BitwardenUri>,
+}
+
+#[derive(Deserialize)]
+struct BitwardenUri {
+    uri: Option<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct BitwardenCard {
+    cardholder_name: Option<String>,
+    number: Option<String>,
+    exp_month: Option<String>,
+    exp_year: Option<String>,
+    code: Option<String>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct BitwardenIdentity {
+    first_name: Option<String>,
+    middle_name: Option<String>,
+    last_name: Option<String>,
+    email: Option<String>,
+    phone: Option<String>,
+    address1: Option<String>,
+    address2: Option<String>,
+    city: Option<String>,
+    state: Option<String>,
+    postal_code: Option<String>,
+    country: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct BitwardenField {
+    name: Option<String>,
+    value: Option<String>,
+    /// 0 text, 1 hidden, 2 boolean
+    #[serde(rename = "type")]
+    kind: u8,
+}
+
+fn parse_bitwarden_json(data: &str) -> anyhow::Result<Imported> {
+    let export: BitwardenExport = serde_json::from_str(data)
+        .map_err(|e| anyhow::anyhow!("❌ Not a Bitwarden JSON export: {}", e))?;
+    if export.encrypted {
+        return Err(anyhow::anyhow!(
+            "❌ Encrypted Bitwarden exports are not supported, export as unencrypted JSON"
+        ));
+    }
+
+    let mut imported = Imported::default();
+    for item in export.items {
+        let folder_name = item
+            .folder_id
+            .as_ref()
+            .and_then(|id| export.folders.iter().find(|f| &f.id == id))
+            .map(|f| f.name.as_str());
+
+        let mut credential = match (item.kind, item.login, item.card, item.identity) {
+            (1, Some(login), _, _) => {
+                let mut credential = Credential::new(item.name, login.password.unwrap_or_default());
+                credential.username = login.username;
+                credential.url = login.uris.into_iter().find_map(|u| u.uri);
+                if let Some(totp) = login.totp {
+                    credential.set_field("otp".to_string(), totp, true);
+                }
+                credential
+            }
+            (3, _, Some(card), _) => match card_details(&card) {
+                Ok(details) => Credential::with_details(item.name, ItemDetails::Card(details)),
+                Err(e) => {
+                    // Keep what we have rather than dropping the card
+                    imported.warnings.push(format!(
+                        "Card '{}' imported as a secure note: {}",
+                        item.name, e
+                    ));
+                    let mut credential =
+                        Credential::with_details(item.name, ItemDetails::SecureNote);
+                    for (name, value) in [("number", card.number), ("cvv", card.code)] {
+                        if let Some(value) = value {
+                            credential.set_field(name.to_string(), value, true);
+                        }
+                    }
+                    credential
+                }
+            },
+            (4, _, _, Some(identity)) => {
+                let full_name = [
+                    identity.first_name,
+                    identity.middle_name,
+                    identity.last_name,
+                ]
+                .into_iter()
+                .flatten()
+                .collect::<Vec<_>>()
+                .join(" ");
+                let details = IdentityDetails {
+                    full_name: if full_name.is_empty() {
+                        item.name.clone()
+                    } else {
+                        full_name
+                    },
+                    email: identity.email,
+                    phone: identity.phone,
+                    address_line1: identity.address1,
+                    address_line2: identity.address2,
+                    city: identity.city,
+                    region: identity.state,
+                    postal_code: identity.postal_code,
+                    country: identity.country,
+                };
+                Credential::with_details(item.name, ItemDetails::Identity(details))
+            }
+            (2, _, _, _) => Credential::with_details(item.name, ItemDetails::SecureNote),
+            (kind, _, _, _) => {
+                imported.warnings.push(format!(
+                    "Item '{}' has unsupported type {}, skipped",
+                    item.name, kind
+                ));
+                continue;
+            }
+        };
+
+        credential.notes = item.notes;
+        credential.folder = match folder_name {
+            Some(name) => folder::normalize(name)?,
+            None => None,
+        };
+        for field in item.fields {
+            if let (Some(name), Some(value)) = (field.name, field.value) {
+                credential.set_field(name, value, field.kind == 1);
+            }
+        }
+
+        imported.credentials.push(credential);
+    }
+
+    Ok(imported)
+}
+
+fn card_details(card: &BitwardenCard) -> anyhow::Result<CardDetails> {
+    let number = card.number.as_deref().unwrap_or_default();
+    let expiry = format!(
+        "{}/{}",
+        card.exp_month.as_deref().unwrap_or_default(),
+        card.exp_year.as_deref().unwrap_or_default()
+    );
+    let cvv = card.code.as_deref().unwrap_or_default();
+    CardDetails::new(card.cardholder_name.clone(), number, &expiry, cvv)
+}
+
+/// Minimal RFC 4180 reader: quoted fields, doubled quotes and embedded newlines
+fn parse_csv(data: &str) -> anyhow::Result<Vec<Vec<String>>> {
+    let mut rows = Vec::new();
+    let mut row = Vec::new();
+    let mut field = String::new();
+    let mut in_quotes = false;
+    let mut chars = data.trim_start_matches('\u{feff}').chars().peekable();
+
+    while let Some(c) = chars.next() {
+        match (c, in_quotes) {
+            ('"', true) if chars.peek() == Some(&'"') => {
+                field.push('"');
+                chars.next();
+            }
+            ('"', true) => in_quotes = false,
+            ('"', false) if field.is_empty() => in_quotes = true,
+            (',', false) => row.push(std::mem::take(&mut field)),
+            ('\r', false) => {}
+            ('\n', false) => {
+                row.push(std::mem::take(&mut field));
+                rows.push(std::mem::take(&mut row));
+            }
+            (c, _) => field.push(c),
+        }
+    }
+
+    if in_quotes {
+        return Err(anyhow::anyhow!("❌ CSV ends inside a quoted field"));
+    }
+    if !field.is_empty() || !row.is_empty() {
+        row.push(field);
+        rows.push(row);
+    }
+
+    Ok(rows)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_keepass_csv_groups_become_folders() {
+        let csv = "\"Group\",\"Title\",\"Username\",\"Password\",\"URL\",\"Notes\"\n\
+                   \"Root/Work/AWS\",\"Console\",\"admin\",\"p,w\"\"1\",\"\",\"line1\nline2\"\n\
+                   \"Root\",\"Email\",\"me\",\"pw2\",\"https://mail\",\"\"\n";
+
+        let imported = parse(ImportFormat::KeepassCsv, csv).unwrap();
+        let [console, email] = &imported.credentials[..] else {
+            panic!("expected two credentials");
+        };
+
+        assert_eq!(console.folder.as_deref(), Some("Work/AWS"));
+        assert_eq!(console.password, "p,w\"1");
+        assert_eq!(console.notes.as_deref(), Some("line1\nline2"));
+        assert_eq!(console.url, None);
+        assert_eq!(email.folder, None);
+        assert_eq!(email.url.as_deref(), Some("https://mail"));
+    }
+
+    #[test]
+    fn test_bitwarden_json_folders_and_types() {
+        let json = r#"{
+            "encrypted": false,
+            "folders": [{"id": "f1", "name": "Work/Cloud"}],
+            "items": [
+                {"type": 1, "name": "AWS", "folderId": "f1", "notes": null,
+                 "login": {"username": "admin", "password": "pw", "totp": "JBSWY3DP",
+                           "uris": [{"uri": "https://aws.amazon.com"}]},
+                 "fields": [{"name": "account", "value": "1234", "type": 0}]},
+                {"type": 3, "name": "Visa", "folderId": null,
+                 "card": {"cardholderName": "Jane", "number": "4111111111111111",
+                          "expMonth": "7", "expYear": "2029", "code": "123"}},
+                {"type": 3, "name": "Broken card",
+                 "card": {"number": "1234", "code": "1"}}
+            ]
+        }"#;
+
+        let imported = parse(ImportFormat::BitwardenJson, json).unwrap();
+        let [aws, visa, broken] = &imported.credentials[..] else {
+            panic!("expected three credentials");
+        };
+
+        assert_eq!(aws.folder.as_deref(), Some("Work/Cloud"));
+        assert_eq!(aws.url.as_deref(), Some("https://aws.amazon.com"));
+        assert!(aws.field("otp").unwrap().concealed);
+        assert_eq!(aws.field("account").unwrap().value, "1234");
+        assert!(matches!(&visa.details, ItemDetails::Card(c) if c.expiry() == "07/2029"));
+        assert_eq!(broken.kind(), crate::models::item::ItemKind::SecureNote);
+        assert_eq!(imported.warnings.len(), 1);
+    }
+}
@@ -1 +1,6 @@
+pub mod attachments;
+pub mod config;
 pub mod file;
+pub mod git;
+pub mod import;
+pub mod remote;
@@ -0,0 +1,351 @@
+use crate::commands::edit::prompt_changes;
+use crate::commands::{Copied, copy_secret};
+use crate::models::credential::{Credential, CredentialVault};
+use crate::models::query::Query;
+use crate::models::search::{self, SearchField};
+use crate::storage::file::FileStorage;
+use crate::utils::Utc;
+use crate::utils::totp::Totp;
+use anyhow::Result;
+use ratatui::widgets::ListState;
+use std::time::{Duration, Instant};
+use uuid::Uuid;
+
+/// Revealed secrets are masked again after this long
+pub const REVEAL_DURATION: Duration = Duration::from_secs(15);
+
+/// How long a status message stays in the bottom bar
+const STATUS_DURATION: Duration = Duration::from_secs(4);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Mode {
+    Browse,
+    Search,
+    ConfirmDelete,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CopyTarget {
+    Username,
+    Password,
+    Otp,
+}
+
+/// One row of the list pane: the credential and the matched service characters
+pub struct Entry {
+    pub id: Uuid,
+    pub positions: Vec<usize>,
+}
+
+pub struct App {
+    storage: FileStorage,
+    master_password: String,
+    pub vault: CredentialVault,
+    pub mode: Mode,
+    pub search: String,
+    pub search_error: Option<String>,
+    pub entries: Vec<Entry>,
+    pub list_state: ListState,
+    revealed: Option<(Uuid, Instant)>,
+    status: Option<(String, Instant)>,
+    edit_requested: bool,
+    pub quit: bool,
+}
+
+impl App {
+    pub fn new(storage: FileStorage, vault: CredentialVault, master_password: String) -> Self {
+        let mut app = Self {
+            storage,
+            master_password,
+            vault,
+            mode: Mode::Browse,
+            search: String::new(),
+            search_error: None,
+            entries: Vec::new(),
+            list_state: ListState::default(),
+            revealed: None,
+            status: None,
+            edit_requested: false,
+            quit: false,
+        };
+        app.refresh();
+        app
+    }
+
+    pub fn selected(&self) -> Option<&Credential> {
+        let entry = self.entries.get(self.list_state.selected()?)?;
+        self.vault.find_credential(&entry.id)
+    }
+
+    /// Rebuild the list from the search box, keeping the selection where possible
+    pub fn refresh(&mut self) {
+        let current = self.selected().map(|c| c.id);
+
+        if self.search.trim().is_empty() {
+            let mut credentials: Vec<_> = self.vault.credentials.iter().collect();
+            credentials.sort_by_key(|c| c.service.to_lowercase());
+            self.entries = credentials
+                .into_iter()
+                .map(|c| Entry {
+                    id: c.id,
+                    positions: Vec::new(),
+                })
+                .collect();
+            self.search_error = None;
+        } else {
+            // Half-typed queries ("tag:", "(a OR") keep the previous results
+            match Query::parse(&self.search) {
+                Ok(query) => {
+                    self.entries = search::find(&self.vault, &query, false)
+                        .iter()
+                        .map(|hit| Entry {
+                            id: hit.credential.id,
+                            positions: hit.positions(SearchField::Service).to_vec(),
+                        })
+                        .collect();
+                    self.search_error = None;
+                }
+                Err(e) => self.search_error = Some(e.to_string()),
+            }
+        }
+
+        let index = current
+            .and_then(|id| self.entries.iter().position(|e| e.id == id))
+            .unwrap_or(0);
+        self.select(index);
+    }
+
+    fn select(&mut self, index: usize) {
+        if self.entries.is_empty() {
+            self.list_state.select(None);
+        } else {
+            self.list_state
+                .select(Some(index.min(self.entries.len() - 1)));
+        }
+        // Moving away hides whatever was revealed
+        if self.revealed.map(|(id, _)| id) != self.selected().map(|c| c.id) {
+            self.revealed = None;
+        }
+    }
+
+    pub fn move_by(&mut self, delta: isize) {
+        let index = self.list_state.selected().unwrap_or(0);
+        self.select(index.saturating_add_signed(delta));
+    }
+
+    pub fn select_first(&mut self) {
+        self.select(0);
+    }
+
+    pub fn select_last(&mut self) {
+        self.select(self.entries.len().saturating_sub(1));
+    }
+
+    pub fn push_search(&mut self, c: char) {
+        self.search.push(c);
+        self.refresh();
+    }
+
+    pub fn pop_search(&mut self) {
+        self.search.pop();
+        self.refresh();
+    }
+
+    pub fn clear_search(&mut self) {
+        self.search.clear();
+        self.refresh();
+    }
+
+    pub fn is_revealed(&self) -> bool {
+        self.revealed.is_some()
+    }
+
+    /// Seconds until revealed secrets are hidden again
+    pub fn reveal_remaining(&self) -> Option<u64> {
+        self.revealed
+            .map(|(_, at)| REVEAL_DURATION.saturating_sub(at.elapsed()).as_secs() + 1)
+    }
+
+    pub fn toggle_reveal(&mut self) {
+        self.revealed = match (self.revealed, self.selected()) {
+            (None, Some(credential)) => Some((credential.id, Instant::now())),
+            _ => None,
+        };
+    }
+
+    pub fn status(&self) -> Option<&str> {
+        self.status.as_ref().map(|(message, _)| message.as_str())
+    }
+
+    pub fn set_status(&mut self, message: impl Into<String>) {
+        self.status = Some((message.into(), Instant::now()));
+    }
+
+    /// Expire the reveal and status message; called before every redraw
+    pub fn tick(&mut self) {
+        if self
+            .revealed
+            .is_some_and(|(_, at)| at.elapsed() >= REVEAL_DURATION)
+        {
+            self.revealed = None;
+        }
+        if self
+            .status
+            .as_ref()
+            .is_some_and(|(_, at)| at.elapsed() >= STATUS_DURATION)
+        {
+            self.status = None;
+        }
+    }
+
+    pub fn copy(&mut self, target: CopyTarget) {
+        let Some(credential) = self.selected() else {
+            return;
+        };
+
+        let value = match target {
+            CopyTarget::Username => credential.username.clone(),
+            CopyTarget::Password => {
+                Some(credential.primary_secret().to_string()).filter(|secret| !secret.is_empty())
+            }
+            CopyTarget::Otp => match credential.otp_field().map(|f| Totp::parse(&f.value)) {
+                Some(Ok(totp)) => Some(totp.now().0),
+                Some(Err(e)) => {
+                    self.set_status(e.to_string());
+                    return;
+                }
+                None => None,
+            },
+        };
+        let what = match target {
+            CopyTarget::Username => "Username",
+            CopyTarget::Password => "Password",
+            CopyTarget::Otp => "One-time code",
+        };
+
+        // Usernames are not secret, so only passwords and codes get cleared again
+        let clear_after = (target == CopyTarget::Username).then_some(0);
+        match value {
+            Some(value) => match copy_secret(&self.storage, &value, clear_after, None) {
+                Ok(Copied {
+                    clear_after: Some(seconds),
+                    ..
+                }) => self.set_status(format!(
+                    "📝 {} copied to clipboard (cleared in {}s)",
+                    what, seconds
+                )),
+                Ok(_) => self.set_status(format!("📝 {} copied to clipboard", what)),
+                Err(e) => self.set_status(format!("⚠️ Failed to copy to clipboard: {}", e)),
+            },
+            None => self.set_status(format!("❌ {} is not set for this entry", what)),
+        }
+    }
+
+    pub fn request_delete(&mut self) {
+        if self.selected().is_some() {
+            self.mode = Mode::ConfirmDelete;
+        }
+    }
+
+    pub fn delete_selected(&mut self) -> Result<()> {
+        self.mode = Mode::Browse;
+        let Some(id) = self.selected().map(|c| c.id) else {
+            return Ok(());
+        };
+        let Some(removed) = self.vault.remove_credential(&id) else {
+            return Ok(());
+        };
+
+        // Drop the blobs only once the vault no longer references them
+        self.storage
+            .write_vault(&self.vault, &self.master_password)?;
+        let store = self.storage.attachments();
+        for attachment in &removed.attachments {
+            store.remove(attachment)?;
+        }
+        self.storage.commit_if_tracked("Update vault");
+
+        self.refresh();
+        self.set_status(format!("✅ Deleted '{}'", removed.service));
+        Ok(())
+    }
+
+    pub fn request_edit(&mut self) {
+        self.edit_requested = self.selected().is_some();
+    }
+
+    pub fn take_edit_request(&mut self) -> bool {
+        std::mem::take(&mut self.edit_requested)
+    }
+
+    /// Run the interactive `edit` prompts; the caller leaves the full-screen UI first
+    pub fn edit_selected(&mut self) -> Result<()> {
+        let Some(mut credential) = self.selected().cloned() else {
+            return Ok(());
+        };
+
+        println!("✏️ Editing credential: {}", credential.service);
+        prompt_changes(&mut credential)?;
+        credential.updated_at = Utc::now();
+
+        let service = credential.service.clone();
+        if let Some(slot) = self.vault.find_credential_mut(&credential.id) {
+            *slot = credential;
+        }
+        self.vault.updated_at = Utc::now();
+        self.storage
+            .write_vault(&self.vault, &self.master_password)?;
+        self.storage.commit_if_tracked("Update vault");
+
+        self.refresh();
+        self.set_status(format!("✅ Updated '{}'", service));
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::path::PathBuf;
+
+    fn app(services: &[&str]) -> App {
+        let mut vault = CredentialVault::new();
+        for service in services {
+            vault.add_credentail(Credential::new(service.to_string(), "pw".to_string()));
+        }
+        let storage = FileStorage::from_path(PathBuf::from("/nonexistent/vault.vault"));
+        App::new(storage, vault, "master".to_string())
+    }
+
+    #[test]
+    fn test_search_keeps_selection() {
+        let mut app = app(&["Zoom", "GitHub", "GitLab"]);
+        // Sorted by service when the search box is empty
+        assert_eq!(app.selected().unwrap().service, "GitHub");
+
+        app.move_by(1);
+        assert_eq!(app.selected().unwrap().service, "GitLab");
+        for c in "gitl".chars() {
+            app.push_search(c);
+        }
+        assert_eq!(app.entries.len(), 1);
+        assert_eq!(app.selected().unwrap().service, "GitLab");
+
+        // A half-typed query keeps the last results
+        app.clear_search();
+        app.push_search('(');
+        assert!(app.search_error.is_some());
+        assert_eq!(app.entries.len(), 3);
+    }
+
+    #[test]
+    fn test_reveal_hides_when_moving() {
+        let mut app = app(&["A", "B"]);
+        app.toggle_reveal();
+        assert!(app.is_revealed());
+        app.move_by(1);
+        assert!(!app.is_revealed());
+        app.move_by(-5);
+        assert_eq!(app.selected().unwrap().service, "A");
+    }
+}
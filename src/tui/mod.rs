@@ -0,0 +1,110 @@
+// Full-screen vault browser behind `pwdmgr tui`
+mod app;
+mod ui;
+
+use crate::models::credential::CredentialVault;
+use crate::storage::file::FileStorage;
+use anyhow::Result;
+use app::{App, CopyTarget, Mode};
+use ratatui::DefaultTerminal;
+use ratatui::crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use ratatui::crossterm::execute;
+use ratatui::crossterm::terminal::{EnterAlternateScreen, enable_raw_mode};
+use std::time::Duration;
+
+/// Redraw interval while idle, so reveal and OTP countdowns keep moving
+const TICK: Duration = Duration::from_millis(250);
+
+/// Browse an unlocked vault until the user quits; changes are written through `storage`
+pub fn run(storage: FileStorage, vault: CredentialVault, master_password: String) -> Result<()> {
+    let mut app = App::new(storage, vault, master_password);
+    let mut terminal = ratatui::init();
+    let result = event_loop(&mut terminal, &mut app);
+    ratatui::restore();
+    result
+}
+
+fn event_loop(terminal: &mut DefaultTerminal, app: &mut App) -> Result<()> {
+    while !app.quit {
+        app.tick();
+        terminal.draw(|frame| ui::draw(frame, app))?;
+
+        if !event::poll(TICK)? {
+            continue;
+        }
+        if let Event::Key(key) = event::read()?
+            && key.kind == KeyEventKind::Press
+        {
+            handle_key(app, key);
+        }
+
+        if app.take_edit_request() {
+            // The edit prompts run on the normal screen, like `pwdmgr edit`
+            ratatui::restore();
+            let result = app.edit_selected();
+            enable_raw_mode()?;
+            execute!(std::io::stdout(), EnterAlternateScreen)?;
+            terminal.clear()?;
+            if let Err(e) = result {
+                app.set_status(format!("❌ Edit failed: {}", e));
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn handle_key(app: &mut App, key: KeyEvent) {
+    if key.modifiers.contains(KeyModifiers::CONTROL) && key.code == KeyCode::Char('c') {
+        app.quit = true;
+        return;
+    }
+
+    match app.mode {
+        Mode::Search => match key.code {
+            KeyCode::Esc => {
+                app.clear_search();
+                app.mode = Mode::Browse;
+            }
+            KeyCode::Enter => app.mode = Mode::Browse,
+            KeyCode::Backspace => app.pop_search(),
+            KeyCode::Up => app.move_by(-1),
+            KeyCode::Down => app.move_by(1),
+            KeyCode::Char('u') if key.modifiers.contains(KeyModifiers::CONTROL) => {
+                app.clear_search()
+            }
+            KeyCode::Char(c) => app.push_search(c),
+            _ => {}
+        },
+        Mode::ConfirmDelete => match key.code {
+            KeyCode::Char('y') | KeyCode::Char('Y') => {
+                if let Err(e) = app.delete_selected() {
+                    app.set_status(format!("❌ Delete failed: {}", e));
+                }
+            }
+            _ => {
+                app.mode = Mode::Browse;
+                app.set_status("❌ Deletion cancelled.");
+            }
+        },
+        Mode::Browse => match key.code {
+            KeyCode::Char('q') => app.quit = true,
+            KeyCode::Esc if !app.search.is_empty() => app.clear_search(),
+            KeyCode::Esc => app.quit = true,
+            KeyCode::Char('/') => app.mode = Mode::Search,
+            KeyCode::Up | KeyCode::Char('k') => app.move_by(-1),
+            KeyCode::Down | KeyCode::Char('j') => app.move_by(1),
+            KeyCode::PageUp => app.move_by(-10),
+            KeyCode::PageDown => app.move_by(10),
+            KeyCode::Home | KeyCode::Char('g') => app.select_first(),
+            KeyCode::End | KeyCode::Char('G') => app.select_last(),
+            KeyCode::Char('u') => app.copy(CopyTarget::Username),
+            KeyCode::Char('p') | KeyCode::Char('c') => app.copy(CopyTarget::Password),
+            KeyCode::Char('o') => app.copy(CopyTarget::Otp),
+            KeyCode::Char('r') => app.toggle_reveal(),
+            KeyCode::Char('e') => app.request_edit(),
+            KeyCode::Char('d') | KeyCode::Delete => app.request_delete(),
+            _ => {}
+        },
+    }
+}
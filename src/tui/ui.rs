@@ -0,0 +1,270 @@
+use crate::models::credential::Credential;
+use crate::models::item::{ItemDetails, ItemKind};
+use crate::tui::app::{App, Mode};
+use crate::utils::totp::Totp;
+use ratatui::Frame;
+use ratatui::layout::{Constraint, Layout, Rect};
+use ratatui::style::{Color, Modifier, Style, Stylize};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::{Block, Clear, List, ListItem, Paragraph, Wrap};
+
+const MASK: &str = "••••••••";
+
+const HINTS: &str =
+    "/ search  ↑↓ move  u user  p password  o otp  r reveal  e edit  d delete  q quit";
+
+pub fn draw(frame: &mut Frame, app: &mut App) {
+    let [search_area, main_area, status_area] = Layout::vertical([
+        Constraint::Length(3),
+        Constraint::Min(0),
+        Constraint::Length(1),
+    ])
+    .areas(frame.area());
+    let [list_area, details_area] =
+        Layout::horizontal([Constraint::Percentage(40), Constraint::Percentage(60)])
+            .areas(main_area);
+
+    draw_search(frame, app, search_area);
+    draw_list(frame, app, list_area);
+    draw_details(frame, app, details_area);
+
+    let status = match app.status() {
+        Some(message) => Line::from(message.to_string()),
+        None => Line::from(HINTS).dim(),
+    };
+    frame.render_widget(Paragraph::new(status), status_area);
+
+    if app.mode == Mode::ConfirmDelete {
+        draw_confirm_delete(frame, app);
+    }
+}
+
+fn draw_search(frame: &mut Frame, app: &App, area: Rect) {
+    let active = app.mode == Mode::Search;
+    let title = match &app.search_error {
+        Some(error) => Line::from(format!(" Search: {} ", error)).red(),
+        None => Line::from(" Search "),
+    };
+    let border = if active {
+        Style::new().yellow()
+    } else {
+        Style::new()
+    };
+
+    let text = if app.search.is_empty() && !active {
+        Line::from("press / to search, e.g. gitub or tag:work url:*.aws.com").dim()
+    } else {
+        Line::from(app.search.as_str())
+    };
+    frame.render_widget(
+        Paragraph::new(text).block(Block::bordered().title(title).border_style(border)),
+        area,
+    );
+
+    if active {
+        let x = area.x + 1 + app.search.chars().count() as u16;
+        frame.set_cursor_position((x.min(area.right().saturating_sub(2)), area.y + 1));
+    }
+}
+
+fn draw_list(frame: &mut Frame, app: &mut App, area: Rect) {
+    let items: Vec<ListItem> = app
+        .entries
+        .iter()
+        .filter_map(|entry| {
+            let credential = app.vault.find_credential(&entry.id)?;
+            let mut spans = highlight(&credential.service, &entry.positions);
+            if let Some(username) = &credential.username {
+                spans.push(Span::raw(" "));
+                spans.push(Span::raw(format!("({})", username)).dim());
+            }
+            Some(ListItem::new(Line::from(spans)))
+        })
+        .collect();
+
+    let title = format!(
+        " Credentials ({}/{}) ",
+        app.entries.len(),
+        app.vault.credentials.len()
+    );
+    let list = List::new(items)
+        .block(Block::bordered().title(title))
+        .highlight_style(
+            Style::new()
+                .bg(Color::DarkGray)
+                .add_modifier(Modifier::BOLD),
+        )
+        .highlight_symbol("▶ ");
+    frame.render_stateful_widget(list, area, &mut app.list_state);
+}
+
+/// Bold the fuzzy-matched characters of `text`
+fn highlight<'a>(text: &'a str, positions: &[usize]) -> Vec<Span<'a>> {
+    text.chars()
+        .enumerate()
+        .map(|(i, c)| {
+            let span = Span::raw(c.to_string());
+            if positions.contains(&i) {
+                span.yellow().bold()
+            } else {
+                span
+            }
+        })
+        .collect()
+}
+
+fn draw_details(frame: &mut Frame, app: &App, area: Rect) {
+    let Some(credential) = app.selected() else {
+        let empty = if app.vault.credentials.is_empty() {
+            "📭 No credentials found in vault."
+        } else {
+            "🔍 No credentials match the search."
+        };
+        frame.render_widget(
+            Paragraph::new(empty).block(Block::bordered().title(" Details ")),
+            area,
+        );
+        return;
+    };
+
+    let title = match app.reveal_remaining() {
+        Some(seconds) => format!(" {} (hiding in {}s) ", credential.service, seconds),
+        None => format!(" {} ", credential.service),
+    };
+    frame.render_widget(
+        Paragraph::new(details(credential, app.is_revealed()))
+            .block(Block::bordered().title(title))
+            .wrap(Wrap { trim: false }),
+        area,
+    );
+}
+
+/// The same fields `pwdmgr get` prints, secrets masked unless revealed
+fn details(credential: &Credential, revealed: bool) -> Vec<Line<'static>> {
+    let secret = |value: String| {
+        if revealed {
+            Span::raw(value).red()
+        } else {
+            Span::raw(MASK).dim()
+        }
+    };
+    let mut lines = Vec::new();
+
+    if credential.kind() != ItemKind::Login {
+        lines.push(row("Type", credential.kind().to_string()));
+    }
+    if let Some(folder) = &credential.folder {
+        lines.push(row("Folder", folder.clone()));
+    }
+    if let Some(username) = &credential.username {
+        lines.push(row("Username", username.clone()));
+    }
+    if !credential.password.is_empty() {
+        lines.push(row("Password", secret(credential.password.clone())));
+    }
+    if let Some(url) = &credential.url {
+        lines.push(row("URL", url.clone()));
+    }
+
+    if let ItemDetails::Card(card) = &credential.details {
+        lines.push(row("Brand", card.brand().to_string()));
+    }
+    for (label, value, is_secret) in credential.details.entries() {
+        // Private keys are far too long for the pane
+        let value = if value.contains('\n') {
+            format!("({} lines)", value.lines().count())
+        } else {
+            value
+        };
+        lines.push(match is_secret {
+            true => row(label, secret(value)),
+            false => row(label, value),
+        });
+    }
+
+    let otp = credential.otp_field();
+    if let Some(field) = otp {
+        match Totp::parse(&field.value) {
+            Ok(totp) => {
+                let (code, remaining) = totp.now();
+                let mut line = row("One-time code", secret(code));
+                line.push_span(Span::raw(format!(" ({}s left)", remaining)).dim());
+                lines.push(line);
+            }
+            Err(e) => lines.push(row("One-time code", e.to_string())),
+        }
+    }
+    for field in &credential.fields {
+        if otp.is_some_and(|otp| otp.name == field.name) {
+            continue;
+        }
+        lines.push(match field.concealed {
+            true => row(&field.name, secret(field.value.clone())),
+            false => row(&field.name, field.value.clone()),
+        });
+    }
+
+    if !credential.tags.is_empty() {
+        lines.push(row("Tags", credential.tags.join(", ")));
+    }
+    if !credential.attachments.is_empty() {
+        let names: Vec<_> = credential
+            .attachments
+            .iter()
+            .map(|a| a.name.as_str())
+            .collect();
+        lines.push(row("Attachments", names.join(", ")));
+    }
+    lines.push(row(
+        "Created",
+        credential
+            .created_at
+            .format("%Y-%m-%d %H:%M UTC")
+            .to_string(),
+    ));
+    if credential.created_at != credential.updated_at {
+        lines.push(row(
+            "Updated",
+            credential
+                .updated_at
+                .format("%Y-%m-%d %H:%M UTC")
+                .to_string(),
+        ));
+    }
+
+    if let Some(notes) = &credential.notes {
+        lines.push(Line::default());
+        lines.push(Line::from("Notes:").bold());
+        lines.extend(notes.lines().map(|line| Line::from(line.to_string())));
+    }
+
+    lines
+}
+
+fn row(label: &str, value: impl Into<Span<'static>>) -> Line<'static> {
+    Line::from(vec![Span::raw(format!("{}: ", label)).bold(), value.into()])
+}
+
+fn draw_confirm_delete(frame: &mut Frame, app: &App) {
+    let Some(credential) = app.selected() else {
+        return;
+    };
+    let area = frame
+        .area()
+        .centered(Constraint::Percentage(60), Constraint::Length(5));
+
+    let text = vec![
+        Line::from(format!("Delete '{}'?", credential.service)).bold(),
+        Line::default(),
+        Line::from("y: delete   any other key: cancel").dim(),
+    ];
+    frame.render_widget(Clear, area);
+    frame.render_widget(
+        Paragraph::new(text).centered().block(
+            Block::bordered()
+                .title(" Confirm ")
+                .border_style(Style::new().red()),
+        ),
+        area,
+    );
+}
@@ -0,0 +1,399 @@
+// Clipboard access through the system clipboard, an OSC 52 terminal escape or
+// a copy command, and clearing copied secrets again after a delay
+
+use anyhow::Result;
+use base64::{Engine as _, engine::general_purpose};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::fmt;
+use std::io::{IsTerminal, Read, Write};
+use std::path::Path;
+use std::process::{Command, Stdio};
+use std::str::FromStr;
+use std::thread;
+use std::time::Duration;
+
+/// Seconds a copied secret stays on the clipboard unless `--clear-after` or the config says otherwise
+pub const DEFAULT_CLEAR_AFTER: u64 = 30;
+
+/// Copy/paste command pairs tried when no `clipboard_command` is configured,
+/// with the environment variable that must be set for each to work
+const KNOWN_COMMANDS: [(&str, &str, Option<&str>); 4] = [
+    ("wl-copy", "wl-paste --no-newline", Some("WAYLAND_DISPLAY")),
+    (
+        "xclip -selection clipboard",
+        "xclip -selection clipboard -o",
+        Some("DISPLAY"),
+    ),
+    (
+        "xsel --clipboard --input",
+        "xsel --clipboard --output",
+        Some("DISPLAY"),
+    ),
+    ("pbcopy", "pbpaste", None),
+];
+
+/// What `--clipboard-backend` and the `clipboard_backend` config key accept
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum BackendChoice {
+    #[default]
+    Auto,
+    Native,
+    Osc52,
+    Command,
+}
+
+impl FromStr for BackendChoice {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "auto" => Ok(BackendChoice::Auto),
+            "native" | "system" => Ok(BackendChoice::Native),
+            "osc52" | "terminal" => Ok(BackendChoice::Osc52),
+            "command" => Ok(BackendChoice::Command),
+            _ => Err(anyhow::anyhow!(
+                "❌ Unknown clipboard backend '{}'. Use auto, native, osc52 or command",
+                s
+            )),
+        }
+    }
+}
+
+/// A resolved way of reaching the clipboard
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Backend {
+    /// The desktop clipboard through the `clipboard` crate (X11, macOS, Windows)
+    Native,
+    /// OSC 52 escape sequence; the terminal sets its host's clipboard, so it works over SSH
+    Osc52,
+    /// Pipe to a command such as `wl-copy`, reading back with `paste` when known
+    Command { copy: String, paste: Option<String> },
+}
+
+impl fmt::Display for Backend {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Backend::Native => write!(f, "system clipboard"),
+            Backend::Osc52 => write!(f, "terminal clipboard (OSC 52)"),
+            Backend::Command { copy, .. } => write!(f, "`{}`", copy),
+        }
+    }
+}
+
+impl Backend {
+    /// Pick a backend. `command` and `paste` are the configured copy/paste commands.
+    ///
+    /// Auto prefers the terminal over SSH, then a desktop clipboard
+    /// (wl-copy on Wayland, the native clipboard on X11), then any known copy
+    /// command on the PATH, and finally OSC 52 when attached to a terminal.
+    pub fn resolve(
+        choice: BackendChoice,
+        command: Option<&str>,
+        paste: Option<&str>,
+    ) -> Result<Self> {
+        let configured = command.map(|copy| Backend::Command {
+            copy: copy.to_string(),
+            paste: paste.map(str::to_string),
+        });
+
+        match choice {
+            BackendChoice::Native => return Ok(Backend::Native),
+            BackendChoice::Osc52 => return Ok(Backend::Osc52),
+            BackendChoice::Command => {
+                return configured.or_else(detect_command).ok_or_else(|| {
+                    anyhow::anyhow!(
+                        "❌ No clipboard command found. Set \"clipboard_command\" in config.json (e.g. \"wl-copy\")"
+                    )
+                });
+            }
+            BackendChoice::Auto => {}
+        }
+
+        if let Some(backend) = configured {
+            return Ok(backend);
+        }
+        let has_tty = std::io::stderr().is_terminal();
+        if has_tty && is_set("SSH_TTY") {
+            return Ok(Backend::Osc52);
+        }
+        if is_set("WAYLAND_DISPLAY")
+            && let Some(backend) = detect_command()
+        {
+            return Ok(backend);
+        }
+        if cfg!(feature = "clipboard") && (is_set("DISPLAY") || cfg!(not(target_os = "linux"))) {
+            return Ok(Backend::Native);
+        }
+        if let Some(backend) = detect_command() {
+            return Ok(backend);
+        }
+        if has_tty {
+            return Ok(Backend::Osc52);
+        }
+        Err(anyhow::anyhow!(
+            "❌ No clipboard available. Try --clipboard-backend osc52 or set \"clipboard_command\""
+        ))
+    }
+
+    /// Put text on the clipboard
+    pub fn copy(&self, content: &str) -> Result<()> {
+        match self {
+            Backend::Native => native_copy(content),
+            Backend::Osc52 => {
+                let mut tty = terminal()?;
+                write_osc52(&mut tty, content, is_set("TMUX"))?;
+                Ok(tty.flush()?)
+            }
+            Backend::Command { copy, .. } => {
+                let (program, args) = split_command(copy)?;
+                let mut child = Command::new(program)
+                    .args(args)
+                    .stdin(Stdio::piped())
+                    .stdout(Stdio::null())
+                    .spawn()
+                    .map_err(|e| anyhow::anyhow!("Failed to run '{}': {}", copy, e))?;
+                if let Some(mut stdin) = child.stdin.take() {
+                    stdin.write_all(content.as_bytes())?;
+                }
+                if !child.wait()?.success() {
+                    return Err(anyhow::anyhow!("'{}' failed", copy));
+                }
+                Ok(())
+            }
+        }
+    }
+
+    /// Current clipboard text, or `None` when this backend cannot read it back
+    pub fn read(&self) -> Result<Option<String>> {
+        match self {
+            Backend::Native => native_read().map(Some),
+            Backend::Osc52 | Backend::Command { paste: None, .. } => Ok(None),
+            Backend::Command {
+                paste: Some(paste), ..
+            } => {
+                let (program, args) = split_command(paste)?;
+                let output = Command::new(program).args(args).output()?;
+                if !output.status.success() {
+                    return Err(anyhow::anyhow!("'{}' failed", paste));
+                }
+                Ok(Some(String::from_utf8_lossy(&output.stdout).into_owned()))
+            }
+        }
+    }
+
+    /// Arguments for the hidden `clipboard-clear` command to rebuild this backend
+    fn to_args(&self) -> Vec<String> {
+        match self {
+            Backend::Native => vec!["--backend".into(), "native".into()],
+            Backend::Osc52 => vec!["--backend".into(), "osc52".into()],
+            Backend::Command { copy, paste } => {
+                let mut args = vec!["--backend".into(), "command".into()];
+                args.extend(["--copy-command".into(), copy.clone()]);
+                if let Some(paste) = paste {
+                    args.extend(["--paste-command".into(), paste.clone()]);
+                }
+                args
+            }
+        }
+    }
+}
+
+fn is_set(var: &str) -> bool {
+    std::env::var_os(var).is_some_and(|value| !value.is_empty())
+}
+
+/// First known copy command that is installed and usable in this session
+fn detect_command() -> Option<Backend> {
+    KNOWN_COMMANDS
+        .iter()
+        .filter(|(_, _, env)| env.is_none_or(is_set))
+        .find(|(copy, _, _)| copy.split_whitespace().next().is_some_and(in_path))
+        .map(|(copy, paste, _)| Backend::Command {
+            copy: copy.to_string(),
+            paste: Some(paste.to_string()),
+        })
+}
+
+fn in_path(program: &str) -> bool {
+    std::env::var_os("PATH")
+        .is_some_and(|path| std::env::split_paths(&path).any(|dir| dir.join(program).is_file()))
+}
+
+fn split_command(command: &str) -> Result<(&str, std::str::SplitWhitespace<'_>)> {
+    let mut words = command.split_whitespace();
+    let program = words
+        .next()
+        .ok_or_else(|| anyhow::anyhow!("❌ Clipboard command is empty"))?;
+    Ok((program, words))
+}
+
+/// The controlling terminal, so OSC 52 still works with stdout redirected
+fn terminal() -> Result<std::fs::File> {
+    std::fs::OpenOptions::new()
+        .write(true)
+        .open(Path::new("/dev/tty"))
+        .map_err(|e| anyhow::anyhow!("No terminal for OSC 52: {}", e))
+}
+
+/// Write the OSC 52 "set clipboard" sequence; empty content clears it.
+/// Inside tmux the sequence is wrapped so tmux passes it on to the outer terminal.
+fn write_osc52(out: &mut impl Write, content: &str, tmux: bool) -> std::io::Result<()> {
+    let payload = general_purpose::STANDARD.encode(content.as_bytes());
+    if tmux {
+        write!(out, "\x1bPtmux;\x1b\x1b]52;c;{}\x07\x1b\\", payload)
+    } else {
+        write!(out, "\x1b]52;c;{}\x07", payload)
+    }
+}
+
+#[cfg(feature = "clipboard")]
+fn native_copy(content: &str) -> Result<()> {
+    use clipboard::{ClipboardContext, ClipboardProvider};
+
+    let mut ctx: ClipboardContext = ClipboardProvider::new()
+        .map_err(|e| anyhow::anyhow!("Faild to initialize Clioboard: {}", e))?;
+
+    ctx.set_contents(content.to_string())
+        .map_err(|e| anyhow::anyhow!("Faild to set clipboard contents: {}", e))?;
+
+    Ok(())
+}
+
+#[cfg(not(feature = "clipboard"))]
+fn native_copy(_content: &str) -> Result<()> {
+    Err(anyhow::anyhow!("Clipboard feature not enabled"))
+}
+
+#[cfg(feature = "clipboard")]
+fn native_read() -> Result<String> {
+    use clipboard::{ClipboardContext, ClipboardProvider};
+
+    let mut ctx: ClipboardContext = ClipboardProvider::new()
+        .map_err(|e| anyhow::anyhow!("Failed to initialize clipboard: {}", e))?;
+
+    ctx.get_contents()
+        .map_err(|e| anyhow::anyhow!("Failed to read clipboard contents: {}", e))
+}
+
+#[cfg(not(feature = "clipboard"))]
+fn native_read() -> Result<String> {
+    Err(anyhow::anyhow!("Clipboard feature not enabled"))
+}
+
+/// SHA-256 of the copied text, so the clearing process never holds the secret itself
+fn fingerprint(content: &str) -> String {
+    hex::encode(Sha256::digest(content.as_bytes()))
+}
+
+/// Start a background `pwdmgr clipboard-clear` that empties the clipboard after
+/// `seconds`, unless something else has been copied in the meantime
+pub fn schedule_clear(backend: &Backend, content: &str, seconds: u64) -> Result<()> {
+    let mut command = Command::new(std::env::current_exe()?);
+    command
+        .args(["clipboard-clear", "--after", &seconds.to_string()])
+        .args(backend.to_args())
+        .stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null());
+
+    // Own process group, so Ctrl-C in the terminal does not cancel the clear
+    #[cfg(unix)]
+    {
+        use std::os::unix::process::CommandExt;
+        command.process_group(0);
+    }
+
+    let mut child = command.spawn()?;
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin.write_all(fingerprint(content).as_bytes())?;
+    }
+
+    // Reap it if we are still around (e.g. in the shell or TUI) when it finishes
+    thread::spawn(move || child.wait());
+    Ok(())
+}
+
+/// Body of the hidden `clipboard-clear` command: read the fingerprint from
+/// stdin, wait, then clear the clipboard if it still holds the same text.
+/// Backends that cannot read the clipboard back (OSC 52) are cleared regardless.
+pub fn clear_later(
+    seconds: u64,
+    backend: BackendChoice,
+    copy_command: Option<String>,
+    paste_command: Option<String>,
+) -> Result<()> {
+    let backend = Backend::resolve(backend, copy_command.as_deref(), paste_command.as_deref())?;
+    let mut expected = String::new();
+    std::io::stdin().read_to_string(&mut expected)?;
+
+    thread::sleep(Duration::from_secs(seconds));
+
+    let unchanged = match backend.read()? {
+        Some(current) => still_copied(&current, expected.trim()),
+        None => true,
+    };
+    if unchanged {
+        backend.copy("")?;
+    }
+    Ok(())
+}
+
+fn still_copied(current: &str, expected_fingerprint: &str) -> bool {
+    !expected_fingerprint.is_empty() && fingerprint(current) == expected_fingerprint
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_only_clears_our_own_copy() {
+        let copied = fingerprint("hunter2");
+        assert!(!copied.contains("hunter2"));
+        assert!(still_copied("hunter2", &copied));
+        assert!(!still_copied("something the user copied later", &copied));
+        assert!(!still_copied("", ""));
+    }
+
+    #[test]
+    fn test_osc52_sequence() {
+        let mut out = Vec::new();
+        write_osc52(&mut out, "hunter2", false).unwrap();
+        assert_eq!(out, b"\x1b]52;c;aHVudGVyMg==\x07");
+
+        let mut out = Vec::new();
+        write_osc52(&mut out, "hunter2", true).unwrap();
+        assert_eq!(out, b"\x1bPtmux;\x1b\x1b]52;c;aHVudGVyMg==\x07\x1b\\");
+
+        // Clearing sends an empty payload
+        let mut out = Vec::new();
+        write_osc52(&mut out, "", false).unwrap();
+        assert_eq!(out, b"\x1b]52;c;\x07");
+    }
+
+    #[test]
+    fn test_backend_args_round_trip() {
+        let backend = Backend::resolve(
+            "command".parse().unwrap(),
+            Some("xclip -selection clipboard"),
+            Some("xclip -selection clipboard -o"),
+        )
+        .unwrap();
+        assert_eq!(
+            backend.to_args()[..4],
+            [
+                "--backend",
+                "command",
+                "--copy-command",
+                "xclip -selection clipboard"
+            ]
+        );
+        assert_eq!(
+            Backend::resolve(BackendChoice::Osc52, None, None).unwrap(),
+            Backend::Osc52
+        );
+        assert!("pigeon".parse::<BackendChoice>().is_err());
+    }
+}
@@ -33,6 +33,20 @@ impl MasterKey {
         Ok(Self { key })
     }
 
+    /// Fresh random key, used for per-attachment encryption
+    pub fn generate() -> Self {
+        let key: [u8; 32] = Aes256Gcm::generate_key(&mut OsRng).into();
+        Self { key }
+    }
+
+    /// Rebuild a key from its raw bytes
+    pub fn from_bytes(bytes: &[u8]) -> anyhow::Result<Self> {
+        let key: [u8; 32] = bytes
+            .try_into()
+            .map_err(|_| anyhow::anyhow!("Invalid key length"))?;
+        Ok(Self { key })
+    }
+
     pub fn as_bytes(&self) -> &[u8] {
         &self.key
     }
@@ -102,7 +116,7 @@ impl Passwordhasher {
             .hash_password(password.as_bytes(), &salt)
             .map_err(|e| anyhow::anyhow!("Hashing failed:  {}", e))?
             .to_string();
-        println!("salt string: {}, hash: {}", salt.to_string(), hash);
+        println!("salt string: {}, hash: {}", salt, hash);
         Ok((hash, salt.to_string()))
     }
 
@@ -0,0 +1,262 @@
+// Fuzzy string matching used to rank search results
+
+/// A successful match: higher scores are better, positions are char indices into the text
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Match {
+    pub score: i32,
+    pub positions: Vec<usize>,
+}
+
+const SCORE_MATCH: i32 = 16;
+const BONUS_FIRST_CHAR: i32 = 8;
+const BONUS_BOUNDARY: i32 = 10;
+const BONUS_CONSECUTIVE: i32 = 12;
+const PENALTY_GAP: i32 = 2;
+const MAX_GAP_PENALTY: i32 = 10;
+const PENALTY_TYPO: i32 = 24;
+/// Weakest accepted match, per pattern character: letters that only turn up
+/// in order, scattered through unrelated text, average less than this
+const MIN_SCORE_PER_CHAR: i32 = SCORE_MATCH;
+
+/// Case-insensitive fuzzy match of `pattern` against `text`.
+///
+/// Tries a subsequence match first (so "gitub" finds "GitHub"), rewarding
+/// consecutive characters and word starts. When that fails, a single typo
+/// (substitution, transposition, extra or missing char) against one word of
+/// the text is still accepted for patterns of four or more characters.
+/// Matches scoring below `MIN_SCORE_PER_CHAR` per pattern character are dropped.
+pub fn fuzzy_match(pattern: &str, text: &str) -> Option<Match> {
+    let pattern: Vec<char> = lower(pattern.trim());
+    if pattern.is_empty() {
+        return None;
+    }
+    let original: Vec<char> = text.chars().collect();
+    let text_lower = lower(text);
+    let min_score = MIN_SCORE_PER_CHAR * pattern.len() as i32;
+
+    if let Some(positions) = subsequence(&pattern, &text_lower) {
+        let score = score(&original, &positions);
+        if score >= min_score {
+            return Some(Match { score, positions });
+        }
+    }
+
+    typo_match(&pattern, &original, &text_lower).filter(|m| m.score >= min_score)
+}
+
+/// Case-insensitive substring match, for `--exact` searches
+pub fn exact_match(pattern: &str, text: &str) -> Option<Match> {
+    let pattern: Vec<char> = lower(pattern.trim());
+    if pattern.is_empty() {
+        return None;
+    }
+    let original: Vec<char> = text.chars().collect();
+    let text_lower = lower(text);
+
+    let start = text_lower
+        .windows(pattern.len())
+        .position(|window| window == pattern.as_slice())?;
+    let positions: Vec<usize> = (start..start + pattern.len()).collect();
+
+    Some(Match {
+        score: score(&original, &positions),
+        positions,
+    })
+}
+
+fn lower(s: &str) -> Vec<char> {
+    // One char per input char so positions line up with the original text
+    s.chars()
+        .map(|c| c.to_lowercase().next().unwrap_or(c))
+        .collect()
+}
+
+/// Shortest window containing the pattern as a subsequence, preferring a contiguous hit
+fn subsequence(pattern: &[char], text: &[char]) -> Option<Vec<usize>> {
+    if let Some(start) = text
+        .windows(pattern.len())
+        .position(|window| window == pattern)
+    {
+        return Some((start..start + pattern.len()).collect());
+    }
+
+    // Forward pass finds where the first complete match ends
+    let mut k = 0;
+    let mut end = 0;
+    for (i, c) in text.iter().enumerate() {
+        if *c == pattern[k] {
+            k += 1;
+            if k == pattern.len() {
+                end = i;
+                break;
+            }
+        }
+    }
+    if k < pattern.len() {
+        return None;
+    }
+
+    // Backward pass from there tightens the start of the window
+    let mut k = pattern.len();
+    let mut start = end;
+    for i in (0..=end).rev() {
+        if text[i] == pattern[k - 1] {
+            k -= 1;
+            if k == 0 {
+                start = i;
+                break;
+            }
+        }
+    }
+
+    let mut positions = Vec::with_capacity(pattern.len());
+    let mut k = 0;
+    for (i, c) in text.iter().enumerate().take(end + 1).skip(start) {
+        if k < pattern.len() && *c == pattern[k] {
+            positions.push(i);
+            k += 1;
+        }
+    }
+    Some(positions)
+}
+
+fn score(text: &[char], positions: &[usize]) -> i32 {
+    let mut total = 0;
+    let mut previous: Option<usize> = None;
+
+    for &pos in positions {
+        total += SCORE_MATCH;
+
+        if pos == 0 {
+            total += BONUS_FIRST_CHAR + BONUS_BOUNDARY;
+        } else if is_boundary(text[pos - 1], text[pos]) {
+            total += BONUS_BOUNDARY;
+        }
+
+        if let Some(prev) = previous {
+            if pos == prev + 1 {
+                total += BONUS_CONSECUTIVE;
+            } else {
+                let gap = (pos - prev - 1) as i32;
+                total -= (gap * PENALTY_GAP).min(MAX_GAP_PENALTY);
+            }
+        }
+        previous = Some(pos);
+    }
+
+    total
+}
+
+/// Start of a word: after a separator, or a camelCase hump
+fn is_boundary(before: char, current: char) -> bool {
+    !before.is_alphanumeric() || (before.is_lowercase() && current.is_uppercase())
+}
+
+fn typo_match(pattern: &[char], original: &[char], text: &[char]) -> Option<Match> {
+    if pattern.len() < 4 {
+        return None;
+    }
+
+    // Compare against each alphanumeric word of the text
+    let mut best: Option<Match> = None;
+    let mut start = 0;
+    while start < text.len() {
+        if !text[start].is_alphanumeric() {
+            start += 1;
+            continue;
+        }
+        let end = (start..text.len())
+            .find(|&i| !text[i].is_alphanumeric())
+            .unwrap_or(text.len());
+
+        let word = &text[start..end];
+        if edit_distance(pattern, word) == 1 {
+            let positions: Vec<usize> = (start..end).collect();
+            let score = score(original, &positions) - PENALTY_TYPO;
+            if best.as_ref().is_none_or(|b| score > b.score) {
+                best = Some(Match { score, positions });
+            }
+        }
+        start = end;
+    }
+
+    best
+}
+
+/// Optimal string alignment distance (Levenshtein plus adjacent transpositions)
+fn edit_distance(a: &[char], b: &[char]) -> usize {
+    if a.len().abs_diff(b.len()) > 1 {
+        return usize::MAX;
+    }
+
+    let mut rows = vec![vec![0usize; b.len() + 1]; a.len() + 1];
+    for (i, row) in rows.iter_mut().enumerate() {
+        row[0] = i;
+    }
+    for (j, cell) in rows[0].iter_mut().enumerate() {
+        *cell = j;
+    }
+
+    for i in 1..=a.len() {
+        for j in 1..=b.len() {
+            let cost = usize::from(a[i - 1] != b[j - 1]);
+            let mut value = (rows[i - 1][j] + 1)
+                .min(rows[i][j - 1] + 1)
+                .min(rows[i - 1][j - 1] + cost);
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                value = value.min(rows[i - 2][j - 2] + 1);
+            }
+            rows[i][j] = value;
+        }
+    }
+
+    rows[a.len()][b.len()]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_subsequence_finds_missing_letters() {
+        let m = fuzzy_match("gitub", "GitHub").unwrap();
+        assert_eq!(m.positions, vec![0, 1, 2, 4, 5]);
+        assert!(fuzzy_match("xyz", "GitHub").is_none());
+    }
+
+    #[test]
+    fn test_contiguous_and_word_start_matches_score_higher() {
+        let prefix = fuzzy_match("git", "GitHub").unwrap();
+        let inner = fuzzy_match("git", "Legit Tools").unwrap();
+        let scattered = fuzzy_match("git", "Google Interview Tips").unwrap();
+        assert!(prefix.score > inner.score);
+        assert!(inner.score > scattered.score);
+    }
+
+    #[test]
+    fn test_scattered_letters_are_not_a_match() {
+        assert!(fuzzy_match("pay", "PayPal").is_some());
+        // p, a and y do appear in order, but far apart inside words
+        assert!(fuzzy_match("pay", "Spotify Family").is_none());
+        assert!(exact_match("ify", "Spotify Family").is_some());
+    }
+
+    #[test]
+    fn test_single_typo_is_tolerated() {
+        let m = fuzzy_match("githbu", "My GitHub").unwrap();
+        assert_eq!(m.positions, vec![3, 4, 5, 6, 7, 8]);
+        assert!(fuzzy_match("gothub", "GitHub").is_some());
+        assert!(fuzzy_match("gothib", "GitHub").is_none());
+        // Too short to guess at
+        assert!(fuzzy_match("gti", "git").is_none());
+    }
+
+    #[test]
+    fn test_exact_match_requires_substring() {
+        assert_eq!(
+            exact_match("hub", "GitHub").unwrap().positions,
+            vec![3, 4, 5]
+        );
+        assert!(exact_match("gitub", "GitHub").is_none());
+    }
+}
@@ -1,5 +1,70 @@
 use anyhow::Result;
-use rand::{RngCore,rng};
+use rand::{CryptoRng, rng};
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::LazyLock;
+
+/// The EFF large wordlist: 7776 words (one per five dice rolls), 12.9 bits each
+const EFF_LARGE_WORDLIST: &str = include_str!("eff_large_wordlist.txt");
+
+static EFF_WORDS: LazyLock<Vec<String>> = LazyLock::new(|| parse_wordlist(EFF_LARGE_WORDLIST));
+
+/// The built-in passphrase words
+pub fn eff_wordlist() -> &'static [String] {
+    &EFF_WORDS
+}
+
+/// Letters and digits every generated password draws from
+const ALPHANUMERIC: &[u8] = b"abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ0123456789";
+
+/// Symbols used when a policy allows symbols but names no set of its own
+pub const DEFAULT_SYMBOLS: &str = "!@#$%^&*()_-+=[]{}|;:,.<>?/";
+
+/// Length of generated passwords unless asked otherwise
+pub const DEFAULT_LENGTH: usize = 20;
+
+/// Characters that are easily mistaken for each other
+pub const LOOK_ALIKES: &str = "0O1lI";
+
+/// Symbols a passphrase may get one of
+const PASSPHRASE_SYMBOLS: &str = "!@#$%^&*-_+=?";
+
+/// Settings for `generate_passphrase`
+#[derive(Debug, Clone)]
+pub struct PassphraseOptions {
+    pub words: usize,
+    pub separator: String,
+    /// Start every word with a capital letter
+    pub capitalize: bool,
+    /// Append a random digit to a random word
+    pub digit: bool,
+    /// Append a random symbol to a random word
+    pub symbol: bool,
+    /// Words file to use instead of the EFF list
+    pub wordlist: Option<PathBuf>,
+}
+
+impl Default for PassphraseOptions {
+    fn default() -> Self {
+        PassphraseOptions {
+            words: 6,
+            separator: "-".to_string(),
+            capitalize: false,
+            digit: false,
+            symbol: false,
+            wordlist: None,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct Passphrase {
+    pub phrase: String,
+    /// Bits of entropy given the wordlist size and options
+    pub entropy: f64,
+}
+
 /// Genrate a strong. random Password.
 ///
 /// # Arguments
@@ -12,28 +77,443 @@ pub fn genrate_password(length: usize, include_symbols: bool) -> Result<String>
         ));
     }
 
-    let mut charset =
-        String::from("abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ0123456789");
-    let symbols = "!@#$%^&*()_-+=[]{}|;:,.<>?/";
-
+    let mut charset = ALPHANUMERIC.to_vec();
     if include_symbols {
-        charset.push_str(symbols);
+        charset.extend_from_slice(DEFAULT_SYMBOLS.as_bytes());
+    }
+
+    let password = sample(&mut rng(), &charset, length);
+    Ok(password.into_iter().map(char::from).collect())
+}
+
+/// A uniform index below `n`. `next_u32() % n` would favour the low indices
+/// whenever `n` does not divide 2^32, so draws from that uneven tail are retried.
+fn random_index<R: CryptoRng>(rng: &mut R, n: usize) -> usize {
+    let n = n as u64;
+    let zone = (1u64 << 32) / n * n;
+    loop {
+        let draw = u64::from(rng.next_u32());
+        if draw < zone {
+            return (draw % n) as usize;
+        }
+    }
+}
+
+/// `length` independent, uniform picks from `table`
+fn sample<R: CryptoRng, T: Copy>(rng: &mut R, table: &[T], length: usize) -> Vec<T> {
+    (0..length)
+        .map(|_| table[random_index(rng, table.len())])
+        .collect()
+}
+
+/// Site requirements a generated password must meet
+#[derive(Debug, Clone, PartialEq)]
+pub struct Policy {
+    pub min_length: usize,
+    pub max_length: usize,
+    pub min_lower: usize,
+    pub min_upper: usize,
+    pub min_digits: usize,
+    pub min_symbols: usize,
+    /// Allowed symbols; empty means letters and digits only
+    pub symbols: String,
+    /// Leave out 0, O, 1, l and I
+    pub exclude_look_alikes: bool,
+    /// Longest run of one repeated character, e.g. 1 forbids "aa"
+    pub max_consecutive: Option<usize>,
+    /// Only these characters may appear; `None` allows every letter and digit
+    pub allowed: Option<String>,
+    /// Each set needs at least one character in the password
+    pub required: Vec<String>,
+}
+
+impl Default for Policy {
+    fn default() -> Self {
+        Policy {
+            min_length: 8,
+            max_length: 128,
+            min_lower: 0,
+            min_upper: 0,
+            min_digits: 0,
+            min_symbols: 0,
+            symbols: String::new(),
+            exclude_look_alikes: false,
+            max_consecutive: None,
+            allowed: None,
+            required: Vec::new(),
+        }
+    }
+}
+
+impl Policy {
+    /// The characters of one class that the policy allows
+    fn class(&self, chars: &str) -> Vec<char> {
+        let mut seen = HashSet::new();
+        chars
+            .chars()
+            .filter(|c| !(self.exclude_look_alikes && LOOK_ALIKES.contains(*c)))
+            .filter(|c| {
+                self.allowed
+                    .as_ref()
+                    .is_none_or(|allowed| allowed.contains(*c))
+            })
+            .filter(|c| seen.insert(*c))
+            .collect()
     }
 
+    fn classes(&self) -> [(&'static str, Vec<char>, usize); 4] {
+        // Letters and digits already have their own classes
+        let symbols = self.symbols.replace(char::is_alphanumeric, "");
+        [
+            (
+                "lowercase",
+                self.class("abcdefghijklmnopqrstuvwxyz"),
+                self.min_lower,
+            ),
+            (
+                "uppercase",
+                self.class("ABCDEFGHIJKLMNOPQRSTUVWXYZ"),
+                self.min_upper,
+            ),
+            ("digit", self.class("0123456789"), self.min_digits),
+            ("symbol", self.class(&symbols), self.min_symbols),
+        ]
+    }
+
+    /// Clamp `length` into the allowed range and check the policy can be met
+    fn check(&self, length: usize) -> Result<usize> {
+        if self.min_length > self.max_length {
+            return Err(anyhow::anyhow!(
+                "❌ Minimum length {} is above the maximum {}",
+                self.min_length,
+                self.max_length
+            ));
+        }
+        let length = length.clamp(self.min_length, self.max_length);
+        if length == 0 {
+            return Err(anyhow::anyhow!("❌ Password length must be at least 1"));
+        }
+
+        let mut required = 0;
+        for (name, chars, min) in self.classes() {
+            if min > 0 && chars.is_empty() {
+                return Err(anyhow::anyhow!(
+                    "❌ The policy needs {} {} characters but allows none",
+                    min,
+                    name
+                ));
+            }
+            required += min;
+        }
+        let charset = self.charset();
+        for set in &self.required {
+            if !set.chars().any(|c| charset.contains(&c)) {
+                return Err(anyhow::anyhow!(
+                    "❌ The policy requires one of \"{}\" but allows none of them",
+                    set
+                ));
+            }
+            required += 1;
+        }
+        if required > length {
+            return Err(anyhow::anyhow!(
+                "❌ The policy needs {} characters but the password is only {} long",
+                required,
+                length
+            ));
+        }
+        if self.max_consecutive == Some(0) {
+            return Err(anyhow::anyhow!(
+                "❌ Max consecutive characters must be at least 1"
+            ));
+        }
+        Ok(length)
+    }
+
+    /// Every character a generated password may contain
+    fn charset(&self) -> Vec<char> {
+        self.classes()
+            .into_iter()
+            .flat_map(|(_, chars, _)| chars)
+            .collect()
+    }
+
+    /// Whether `password` meets every minimum count and the repeat limit
+    #[cfg(test)]
+    pub fn accepts(&self, password: &[char]) -> bool {
+        let classes_met = self
+            .classes()
+            .iter()
+            .all(|(_, chars, min)| password.iter().filter(|c| chars.contains(c)).count() >= *min);
+        let required_met = self
+            .required
+            .iter()
+            .all(|set| password.iter().any(|c| set.contains(*c)));
+
+        let runs_ok = match self.max_consecutive {
+            Some(max) => password.chunk_by(|a, b| a == b).all(|run| run.len() <= max),
+            None => true,
+        };
+        classes_met && required_met && runs_ok
+    }
+}
+
+/// Generate a password that meets `policy`. Each class's minimum and one
+/// character of every required set are drawn first, the rest comes from all
+/// allowed characters, and a Fisher–Yates shuffle mixes the positions.
+pub fn generate_with_policy(length: usize, policy: &Policy) -> Result<String> {
+    let length = policy.check(length)?;
+    let charset = policy.charset();
+
     let mut rng = rng();
-    let password: String = (0..length)
-        .map(|_| {
-            let idx = rng.next_u32() as usize % charset.len();
-            charset.chars().nth(idx).unwrap()
-        })
+    let mut picks = Picks {
+        chars: Vec::with_capacity(length),
+        counts: HashMap::new(),
+        length,
+        max_consecutive: policy.max_consecutive,
+    };
+    for (_, chars, min) in policy.classes() {
+        for _ in 0..min {
+            picks.pick(&mut rng, &chars)?;
+        }
+    }
+    for set in &policy.required {
+        let set: Vec<char> = charset
+            .iter()
+            .copied()
+            .filter(|c| set.contains(*c))
+            .collect();
+        picks.pick(&mut rng, &set)?;
+    }
+    while picks.chars.len() < length {
+        picks.pick(&mut rng, &charset)?;
+    }
+
+    let mut chars = picks.chars;
+    shuffle(&mut rng, &mut chars);
+    if let Some(max) = policy.max_consecutive {
+        chars = limit_runs(chars, max)?;
+    }
+    Ok(chars.into_iter().collect())
+}
+
+/// Characters drawn so far for a password of `length`
+struct Picks {
+    chars: Vec<char>,
+    counts: HashMap<char, usize>,
+    length: usize,
+    max_consecutive: Option<usize>,
+}
+
+impl Picks {
+    /// Add a uniform pick from `set`, skipping characters that would be too
+    /// many to keep apart under the repeat limit
+    fn pick<R: CryptoRng>(&mut self, rng: &mut R, set: &[char]) -> Result<()> {
+        let fitting: Vec<char> = set
+            .iter()
+            .copied()
+            .filter(|c| {
+                let count = self.counts.get(c).copied().unwrap_or(0) + 1;
+                // `count` copies need `count - 1` gaps, every `max` of them one other character
+                self.max_consecutive
+                    .is_none_or(|max| count <= max * (self.length - count + 1))
+            })
+            .collect();
+        if fitting.is_empty() {
+            return Err(anyhow::anyhow!(
+                "❌ Could not meet the password policy; allow more characters or longer repeats"
+            ));
+        }
+        let c = fitting[random_index(rng, fitting.len())];
+        self.chars.push(c);
+        *self.counts.entry(c).or_default() += 1;
+        Ok(())
+    }
+}
+
+/// Unbiased Fisher–Yates shuffle
+fn shuffle<R: CryptoRng, T>(rng: &mut R, items: &mut [T]) {
+    for i in (1..items.len()).rev() {
+        items.swap(i, random_index(rng, i + 1));
+    }
+}
+
+/// Reorder shuffled characters so no run is longer than `max`. Each position
+/// takes the first remaining character that leaves the rest placeable.
+fn limit_runs(mut remaining: Vec<char>, max: usize) -> Result<Vec<char>> {
+    let mut counts: HashMap<char, usize> = HashMap::new();
+    for &c in &remaining {
+        *counts.entry(c).or_default() += 1;
+    }
+
+    let mut placed: Vec<char> = Vec::with_capacity(remaining.len());
+    let mut run = 0;
+    while !remaining.is_empty() {
+        let last = placed.last().copied();
+        let next_run = |c: char| if Some(c) == last { run + 1 } else { 1 };
+        let index = remaining
+            .iter()
+            .position(|&c| next_run(c) <= max && can_follow(&counts, c, next_run(c), max))
+            .ok_or_else(|| {
+                anyhow::anyhow!(
+                    "❌ Could not keep repeated characters under the limit of {}",
+                    max
+                )
+            })?;
+        let c = remaining.remove(index);
+        run = next_run(c);
+        *counts.get_mut(&c).unwrap() -= 1;
+        placed.push(c);
+    }
+    Ok(placed)
+}
+
+/// Whether the characters in `counts`, less one `next`, can still be placed
+/// after a run of `run` copies of `next` with no run longer than `max`
+fn can_follow(counts: &HashMap<char, usize>, next: char, run: usize, max: usize) -> bool {
+    let total = counts.values().sum::<usize>() - 1;
+    counts.iter().all(|(&c, &count)| {
+        if c == next {
+            let count = count - 1;
+            count <= (max - run) + max * (total - count)
+        } else {
+            count <= max * (total - count + 1)
+        }
+    })
+}
+
+/// Generate a Diceware passphrase such as `cubicle-unshaven-reflex-gusty-pang-wrist`
+pub fn generate_passphrase(options: &PassphraseOptions) -> Result<Passphrase> {
+    match &options.wordlist {
+        Some(path) => generate_passphrase_from(&load_wordlist(path)?, options),
+        None => generate_passphrase_from(eff_wordlist(), options),
+    }
+}
+
+/// Generate a passphrase from an already loaded wordlist, ignoring
+/// `options.wordlist`; lets callers making many passphrases read the file once
+pub fn generate_passphrase_from(
+    wordlist: &[String],
+    options: &PassphraseOptions,
+) -> Result<Passphrase> {
+    if options.words < 3 {
+        return Err(anyhow::anyhow!(
+            "‼️ passphrase too short (must be at least 3 words)"
+        ));
+    }
+
+    let mut rng = rng();
+    let mut words: Vec<String> = (0..options.words)
+        .map(|_| wordlist[random_index(&mut rng, wordlist.len())].clone())
         .collect();
+    let mut entropy = options.words as f64 * (wordlist.len() as f64).log2();
+
+    if options.capitalize {
+        for word in &mut words {
+            let mut chars = word.chars();
+            if let Some(first) = chars.next() {
+                *word = first.to_uppercase().chain(chars).collect();
+            }
+        }
+    }
+
+    // Each extra character lands on a random word, which adds to the entropy
+    let mut extras = Vec::new();
+    if options.digit {
+        extras.push("0123456789");
+    }
+    if options.symbol {
+        extras.push(PASSPHRASE_SYMBOLS);
+    }
+    for pool in extras {
+        let pool: Vec<char> = pool.chars().collect();
+        let word = random_index(&mut rng, words.len());
+        words[word].push(pool[random_index(&mut rng, pool.len())]);
+        entropy += (pool.len() as f64).log2() + (words.len() as f64).log2();
+    }
+
+    Ok(Passphrase {
+        phrase: words.join(&options.separator),
+        entropy,
+    })
+}
+
+/// Read a custom wordlist: one word per line, optionally after dice numbers
+/// as in the EFF files. Duplicates are dropped so the entropy stays honest.
+pub fn load_wordlist(path: &Path) -> Result<Vec<String>> {
+    let text = fs::read_to_string(path)
+        .map_err(|e| anyhow::anyhow!("❌ Could not read wordlist {}: {}", path.display(), e))?;
+    let words = parse_wordlist(&text);
+    if words.len() < 2 {
+        return Err(anyhow::anyhow!(
+            "❌ Wordlist {} needs at least 2 different words",
+            path.display()
+        ));
+    }
+    // stderr, so the warning never mixes with passphrases a script reads
+    if words.len() < 1000 {
+        eprintln!(
+            "⚠️ Wordlist has only {} words ({:.1} bits per word)",
+            words.len(),
+            (words.len() as f64).log2()
+        );
+    }
+    Ok(words)
+}
 
-    Ok(password)
+fn parse_wordlist(text: &str) -> Vec<String> {
+    let mut seen = HashSet::new();
+    text.lines()
+        .filter_map(|line| {
+            let line = line.trim();
+            let word = match line.split_once(char::is_whitespace) {
+                Some((dice, word)) if dice.chars().all(|c| c.is_ascii_digit()) => word.trim(),
+                _ => line,
+            };
+            (!word.is_empty() && !word.starts_with('#')).then(|| word.to_string())
+        })
+        .filter(|word| seen.insert(word.clone()))
+        .collect()
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
+    use rand::rngs::StdRng;
+    use rand::{RngCore, SeedableRng};
+    use std::collections::HashMap;
+    use std::time::Instant;
+
+    /// Replays fixed draws, to see which ones `random_index` rejects
+    struct Replay(Vec<u32>);
+
+    impl RngCore for Replay {
+        fn next_u32(&mut self) -> u32 {
+            self.0.remove(0)
+        }
+
+        fn next_u64(&mut self) -> u64 {
+            u64::from(self.next_u32())
+        }
+
+        fn fill_bytes(&mut self, dest: &mut [u8]) {
+            for chunk in dest.chunks_mut(4) {
+                let bytes = self.next_u32().to_le_bytes();
+                chunk.copy_from_slice(&bytes[..chunk.len()]);
+            }
+        }
+    }
+
+    impl CryptoRng for Replay {}
+
+    /// Pearson's chi-squared statistic of `counts` against a uniform distribution
+    fn chi_squared(counts: &[usize]) -> f64 {
+        let expected = counts.iter().sum::<usize>() as f64 / counts.len() as f64;
+        counts
+            .iter()
+            .map(|&c| (c as f64 - expected).powi(2) / expected)
+            .sum()
+    }
 
     #[test]
     fn test_generate_password_length() {
@@ -46,4 +526,199 @@ mod tests {
         let pass = genrate_password(32, false).unwrap();
         assert!(pass.chars().all(|c| c.is_alphanumeric()))
     }
+
+    #[test]
+    fn test_generate_passphrase() {
+        assert_eq!(eff_wordlist().len(), 7776);
+
+        let passphrase = generate_passphrase(&PassphraseOptions::default()).unwrap();
+        // A few EFF words such as "t-shirt" have a hyphen of their own
+        assert!(passphrase.phrase.split('-').count() >= 6);
+        assert!((passphrase.entropy - 77.5).abs() < 0.1);
+
+        let options = PassphraseOptions {
+            words: 4,
+            separator: " ".to_string(),
+            capitalize: true,
+            digit: true,
+            symbol: true,
+            wordlist: None,
+        };
+        let passphrase = generate_passphrase(&options).unwrap();
+        let words: Vec<&str> = passphrase.phrase.split(' ').collect();
+        assert_eq!(words.len(), 4);
+        assert!(words.iter().all(|w| w.starts_with(char::is_uppercase)));
+        assert!(passphrase.phrase.chars().any(|c| c.is_ascii_digit()));
+        assert!(
+            passphrase
+                .phrase
+                .chars()
+                .any(|c| PASSPHRASE_SYMBOLS.contains(c))
+        );
+
+        let custom = parse_wordlist("# mine\n11 apple\nbanana\n\napple\n");
+        assert_eq!(custom, vec!["apple", "banana"]);
+    }
+
+    #[test]
+    fn test_generate_with_policy() {
+        let policy = Policy {
+            max_length: 20,
+            min_lower: 1,
+            min_upper: 1,
+            min_digits: 2,
+            min_symbols: 1,
+            symbols: "-_".to_string(),
+            exclude_look_alikes: true,
+            max_consecutive: Some(1),
+            ..Default::default()
+        };
+        for _ in 0..200 {
+            let password = generate_with_policy(32, &policy).unwrap();
+            let chars: Vec<char> = password.chars().collect();
+            assert_eq!(chars.len(), 20);
+            assert!(policy.accepts(&chars));
+            assert!(
+                chars
+                    .iter()
+                    .all(|c| c.is_ascii_alphanumeric() || "-_".contains(*c))
+            );
+            assert!(!chars.iter().any(|c| LOOK_ALIKES.contains(*c)));
+        }
+
+        // Requirements that cannot fit are reported instead of looping
+        let tight = Policy {
+            max_length: 4,
+            min_digits: 3,
+            min_upper: 2,
+            ..Default::default()
+        };
+        assert!(
+            generate_with_policy(
+                4,
+                &Policy {
+                    min_length: 4,
+                    ..tight
+                }
+            )
+            .is_err()
+        );
+        let no_symbols = Policy {
+            min_symbols: 1,
+            ..Default::default()
+        };
+        assert!(generate_with_policy(16, &no_symbols).is_err());
+    }
+
+    #[test]
+    fn test_generate_with_tight_policy() {
+        // Half the password must be the only allowed symbol
+        let policy = Policy {
+            min_length: 12,
+            max_length: 12,
+            min_symbols: 6,
+            symbols: "-".to_string(),
+            ..Default::default()
+        };
+        let spread = Policy {
+            max_consecutive: Some(1),
+            ..policy.clone()
+        };
+        for policy in [&policy, &spread] {
+            for _ in 0..200 {
+                let password = generate_with_policy(16, policy).unwrap();
+                let chars: Vec<char> = password.chars().collect();
+                assert_eq!(chars.len(), 12);
+                assert!(chars.iter().filter(|c| **c == '-').count() >= 6);
+                assert!(policy.accepts(&chars));
+            }
+        }
+
+        // Seven dashes in twelve cannot all be kept apart
+        let crowded = Policy {
+            min_symbols: 7,
+            ..spread
+        };
+        assert!(generate_with_policy(12, &crowded).is_err());
+    }
+
+    #[test]
+    fn test_shuffle_is_uniform() {
+        let mut rng = StdRng::seed_from_u64(47);
+        let mut counts: HashMap<[u8; 3], usize> = HashMap::new();
+        for _ in 0..60_000 {
+            let mut items = *b"abc";
+            shuffle(&mut rng, &mut items);
+            *counts.entry(items).or_default() += 1;
+        }
+        let counts: Vec<usize> = counts.into_values().collect();
+        assert_eq!(counts.len(), 6);
+        // 20.5 is the 99.9th percentile of chi-squared with 5 degrees of freedom
+        assert!(chi_squared(&counts) < 20.5, "{}", chi_squared(&counts));
+    }
+
+    #[test]
+    fn test_random_index_rejects_the_uneven_tail() {
+        // 2^32 = 429496729 * 10 + 6, so the top 6 draws would favour 0-5
+        let mut rng = Replay(vec![u32::MAX, u32::MAX - 5, 4294967289, 17]);
+        assert_eq!(random_index(&mut rng, 10), 9);
+        assert_eq!(random_index(&mut rng, 10), 7);
+    }
+
+    #[test]
+    fn test_character_distribution_is_uniform() {
+        let mut rng = StdRng::seed_from_u64(49);
+        let mut charset = ALPHANUMERIC.to_vec();
+        charset.extend_from_slice(DEFAULT_SYMBOLS.as_bytes());
+        let index: HashMap<u8, usize> = charset.iter().enumerate().map(|(i, &c)| (c, i)).collect();
+
+        // Over all characters, and at a single position across passwords
+        let mut overall = vec![0; charset.len()];
+        let mut first = vec![0; charset.len()];
+        for _ in 0..20_000 {
+            let password = sample(&mut rng, &charset, 16);
+            for c in &password {
+                overall[index[c]] += 1;
+            }
+            first[index[&password[0]]] += 1;
+        }
+
+        // 135 is the 99.9th percentile of chi-squared with 88 degrees of freedom
+        assert!(chi_squared(&overall) < 135.0, "{}", chi_squared(&overall));
+        assert!(chi_squared(&first) < 135.0, "{}", chi_squared(&first));
+        // A skewed sample is caught by the same check
+        let mut skewed = overall.clone();
+        skewed[0] += 3000;
+        assert!(chi_squared(&skewed) > 135.0);
+    }
+
+    #[test]
+    #[ignore = "benchmark: cargo test --release bench_ -- --ignored --nocapture"]
+    fn bench_generate_password() {
+        const ROUNDS: u32 = 100_000;
+        let start = Instant::now();
+        for _ in 0..ROUNDS {
+            genrate_password(32, true).unwrap();
+        }
+        println!(
+            "genrate_password(32, true): {:?} each",
+            start.elapsed() / ROUNDS
+        );
+
+        let policy = Policy {
+            min_digits: 2,
+            min_symbols: 2,
+            symbols: DEFAULT_SYMBOLS.to_string(),
+            max_consecutive: Some(1),
+            ..Default::default()
+        };
+        let start = Instant::now();
+        for _ in 0..ROUNDS {
+            generate_with_policy(32, &policy).unwrap();
+        }
+        println!(
+            "generate_with_policy(32, ..): {:?} each",
+            start.elapsed() / ROUNDS
+        );
+    }
 }
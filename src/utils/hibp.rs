@@ -0,0 +1,137 @@
+// Offline lookups in a Have I Been Pwned "Pwned Passwords" SHA-1 dump. The
+// file is sorted by hash with one `HASH:COUNT` line each, so a binary search
+// over byte offsets needs about 30 reads even for the full multi-GB file.
+
+use anyhow::Result;
+use sha1::{Digest, Sha1};
+use std::cmp::Ordering;
+use std::fs::File;
+use std::io::{BufRead, BufReader, Seek, SeekFrom};
+use std::path::Path;
+
+/// Length of a hex SHA-1 hash at the start of each line
+const HASH_LEN: usize = 40;
+
+pub struct HashFile {
+    reader: BufReader<File>,
+    len: u64,
+}
+
+impl HashFile {
+    pub fn open(path: &Path) -> Result<Self> {
+        let file = File::open(path).map_err(|e| {
+            anyhow::anyhow!("❌ Could not open HIBP file {}: {}", path.display(), e)
+        })?;
+        let len = file.metadata()?.len();
+        let mut hashes = HashFile {
+            reader: BufReader::new(file),
+            len,
+        };
+
+        // Catch the NTLM dump or an unrelated file before trusting results
+        match hashes.line_from(0)? {
+            Some((_, line)) if is_hash_line(&line) => Ok(hashes),
+            None => Ok(hashes),
+            Some(_) => Err(anyhow::anyhow!(
+                "❌ {} is not a SHA-1 HIBP file (expected HASH:COUNT lines)",
+                path.display()
+            )),
+        }
+    }
+
+    /// How many times `password` appears in breaches, 0 if never
+    pub fn count(&mut self, password: &str) -> Result<u64> {
+        let hash = hex::encode_upper(Sha1::digest(password.as_bytes()));
+        self.lookup(&hash)
+    }
+
+    fn lookup(&mut self, hash: &str) -> Result<u64> {
+        let (mut low, mut high) = (0, self.len);
+        while low < high {
+            let middle = low + (high - low) / 2;
+            let Some((start, line)) = self.line_from(middle)? else {
+                high = middle;
+                continue;
+            };
+
+            let line_hash = line.get(..HASH_LEN).unwrap_or(&line);
+            match line_hash.to_ascii_uppercase().as_str().cmp(hash) {
+                Ordering::Equal => return Ok(parse_count(&line)),
+                Ordering::Less => low = start + line.len() as u64 + 1,
+                Ordering::Greater => high = middle,
+            }
+        }
+        Ok(0)
+    }
+
+    /// The first complete line starting at or after `offset`, with its start
+    fn line_from(&mut self, offset: u64) -> Result<Option<(u64, String)>> {
+        let mut start = offset;
+        let mut line = Vec::new();
+        if offset > 0 {
+            // Skip the rest of the line `offset` falls into
+            self.reader.seek(SeekFrom::Start(offset - 1))?;
+            start += self.reader.read_until(b'\n', &mut line)? as u64 - 1;
+            line.clear();
+        } else {
+            self.reader.seek(SeekFrom::Start(0))?;
+        }
+        if start >= self.len {
+            return Ok(None);
+        }
+
+        self.reader.read_until(b'\n', &mut line)?;
+        let line = String::from_utf8_lossy(&line);
+        // Drop the newline; a '\r' stays so the length still reaches the next line
+        let line = line.strip_suffix('\n').unwrap_or(&line).to_string();
+        Ok(Some((start, line)))
+    }
+}
+
+fn is_hash_line(line: &str) -> bool {
+    let hash = line.split(':').next().unwrap_or_default();
+    hash.len() == HASH_LEN && hash.chars().all(|c| c.is_ascii_hexdigit())
+}
+
+fn parse_count(line: &str) -> u64 {
+    line.split(':')
+        .nth(1)
+        .and_then(|count| count.trim().parse().ok())
+        .unwrap_or(1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lookup() {
+        let mut hashes: Vec<(String, u64)> = ["password", "123456", "hunter2", "letmein"]
+            .iter()
+            .enumerate()
+            .map(|(i, p)| (hex::encode_upper(Sha1::digest(p.as_bytes())), i as u64 + 10))
+            .collect();
+        // Pad with neighbours so the search has to step over lines
+        for i in 0..200u32 {
+            hashes.push((hex::encode_upper(Sha1::digest(i.to_le_bytes())), 1));
+        }
+        hashes.sort();
+
+        let name = format!("pwdmgr-hibp-{}.txt", uuid::Uuid::new_v4());
+        let path = std::env::temp_dir().join(name);
+        let lines: Vec<String> = hashes
+            .iter()
+            .map(|(h, c)| format!("{}:{}\r\n", h, c))
+            .collect();
+        std::fs::write(&path, lines.concat()).unwrap();
+
+        let mut hibp = HashFile::open(&path).unwrap();
+        assert_eq!(hibp.count("password").unwrap(), 10);
+        assert_eq!(hibp.count("letmein").unwrap(), 13);
+        assert_eq!(hibp.count("v8#Lq2!pZr9mXw").unwrap(), 0);
+        for (hash, count) in &hashes {
+            assert_eq!(hibp.lookup(hash).unwrap(), *count);
+        }
+        std::fs::remove_file(path).unwrap();
+    }
+}
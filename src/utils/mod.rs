@@ -1,4 +1,10 @@
 pub use chrono::{DateTime, Utc};
 
+pub mod clipboard;
 pub mod crypto;
+pub mod fuzzy;
 pub mod generator;
+pub mod hibp;
+pub mod password_rules;
+pub mod strength;
+pub mod totp;
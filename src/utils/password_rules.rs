@@ -0,0 +1,201 @@
+// Parser for the `passwordrules` language that Apple uses to describe site
+// constraints, e.g. `required: lower; required: digit; allowed: [-_];
+// max-consecutive: 2; maxlength: 20`. The rules become a generator `Policy`.
+
+use crate::utils::generator::Policy;
+use anyhow::Result;
+
+const LOWER: &str = "abcdefghijklmnopqrstuvwxyz";
+const UPPER: &str = "ABCDEFGHIJKLMNOPQRSTUVWXYZ";
+const DIGITS: &str = "0123456789";
+/// ASCII punctuation; the spec also counts the space, but generated
+/// passwords leave it out so they survive copy and paste
+const SPECIAL: &str = "-~!@#$%^&*_+=`|(){}[:;\"'<>,.?]\\/";
+
+/// Parse a rule string into the policy a generated password must meet
+pub fn parse(rules: &str) -> Result<Policy> {
+    let mut policy = Policy::default();
+    let mut allowed = String::new();
+    let mut min_length = None;
+
+    for rule in split_rules(rules)? {
+        let (name, value) = rule.split_once(':').ok_or_else(|| {
+            anyhow::anyhow!("❌ Password rule '{}' is missing a ':'", rule.trim())
+        })?;
+        let name = name.trim().to_lowercase();
+        match name.as_str() {
+            "required" => {
+                let set = parse_classes(value)?;
+                allowed.push_str(&set);
+                policy.required.push(set);
+            }
+            "allowed" => allowed.push_str(&parse_classes(value)?),
+            // Repeated limits keep the strictest value, as browsers do
+            "minlength" => {
+                let value = parse_number(&name, value)?;
+                min_length = Some(min_length.map_or(value, |min: usize| min.max(value)));
+            }
+            "maxlength" => {
+                let value = parse_number(&name, value)?;
+                policy.max_length = policy.max_length.min(value);
+            }
+            "max-consecutive" => {
+                let value = parse_number(&name, value)?;
+                policy.max_consecutive =
+                    Some(policy.max_consecutive.map_or(value, |max| max.min(value)));
+            }
+            _ => {
+                return Err(anyhow::anyhow!(
+                    "❌ Unknown password rule '{}'. Use required, allowed, minlength, maxlength or max-consecutive",
+                    name
+                ));
+            }
+        }
+    }
+
+    // Without any class rules every printable character is allowed
+    if allowed.is_empty() {
+        allowed = printable();
+    }
+    policy.symbols = allowed.replace(char::is_alphanumeric, "");
+    policy.allowed = Some(allowed);
+    policy.min_length = min_length.unwrap_or(policy.min_length.min(policy.max_length));
+    Ok(policy)
+}
+
+/// Split on ';' except inside a custom class such as `[;,]`
+fn split_rules(rules: &str) -> Result<Vec<&str>> {
+    let mut parts = Vec::new();
+    let mut start = 0;
+    let mut class_start = None;
+    for (i, c) in rules.char_indices() {
+        match (c, class_start) {
+            ('[', None) => class_start = Some(i),
+            // A ']' right after '[' is a member, not the end of the class
+            (']', Some(open)) if i > open + 1 => class_start = None,
+            (';', None) => {
+                parts.push(&rules[start..i]);
+                start = i + 1;
+            }
+            _ => {}
+        }
+    }
+    if class_start.is_some() {
+        return Err(anyhow::anyhow!("❌ Unclosed '[' in password rules"));
+    }
+    parts.push(&rules[start..]);
+    Ok(parts
+        .into_iter()
+        .filter(|part| !part.trim().is_empty())
+        .collect())
+}
+
+/// Characters of a comma-separated class list like `upper, digit, [-_]`
+fn parse_classes(value: &str) -> Result<String> {
+    let mut set = String::new();
+    let mut chars = value.trim().chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            ',' => {}
+            c if c.is_whitespace() => {}
+            '[' => {
+                let mut class = String::new();
+                if chars.peek() == Some(&']') {
+                    class.extend(chars.next());
+                }
+                loop {
+                    match chars.next() {
+                        Some(']') => break,
+                        Some(c) => class.push(c),
+                        None => return Err(anyhow::anyhow!("❌ Unclosed '[' in password rules")),
+                    }
+                }
+                set.push_str(&class);
+            }
+            c => {
+                let mut name = String::from(c);
+                while let Some(c) = chars.next_if(|c| c.is_alphanumeric() || *c == '-') {
+                    name.push(c);
+                }
+                set.push_str(&match name.to_lowercase().as_str() {
+                    "lower" => LOWER.to_string(),
+                    "upper" => UPPER.to_string(),
+                    "digit" => DIGITS.to_string(),
+                    "special" => SPECIAL.to_string(),
+                    // Only ASCII is generated, so unicode adds nothing more
+                    "ascii-printable" | "unicode" => printable(),
+                    _ => {
+                        return Err(anyhow::anyhow!(
+                            "❌ Unknown character class '{}'. Use lower, upper, digit, special, ascii-printable, unicode or [chars]",
+                            name
+                        ));
+                    }
+                });
+            }
+        }
+    }
+
+    if set.is_empty() {
+        return Err(anyhow::anyhow!(
+            "❌ Empty character class in password rules"
+        ));
+    }
+    Ok(set)
+}
+
+fn printable() -> String {
+    format!("{}{}{}{}", LOWER, UPPER, DIGITS, SPECIAL)
+}
+
+fn parse_number(name: &str, value: &str) -> Result<usize> {
+    value
+        .trim()
+        .parse()
+        .map_err(|_| anyhow::anyhow!("❌ '{}' needs a number, got '{}'", name, value.trim()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::utils::generator::generate_with_policy;
+
+    #[test]
+    fn test_parse_and_generate() {
+        let rules =
+            "required: lower; required: digit; allowed: [-_]; max-consecutive: 2; maxlength: 20";
+        let policy = parse(rules).unwrap();
+        assert_eq!(policy.max_length, 20);
+        assert_eq!(policy.max_consecutive, Some(2));
+        assert_eq!(policy.required, vec![LOWER.to_string(), DIGITS.to_string()]);
+        assert_eq!(policy.symbols, "-_");
+
+        for _ in 0..100 {
+            let password = generate_with_policy(32, &policy).unwrap();
+            assert_eq!(password.len(), 20);
+            assert!(password.chars().any(|c| c.is_ascii_lowercase()));
+            assert!(password.chars().any(|c| c.is_ascii_digit()));
+            assert!(
+                password
+                    .chars()
+                    .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || "-_".contains(c))
+            );
+        }
+
+        // ']' first in a class and ';' inside one are members
+        let policy = parse("required: upper, [];]; minlength: 12;").unwrap();
+        assert_eq!(policy.required, vec![format!("{}];", UPPER)]);
+        assert_eq!(policy.min_length, 12);
+        let password = generate_with_policy(16, &policy).unwrap();
+        assert!(
+            password
+                .chars()
+                .all(|c| c.is_ascii_uppercase() || "];".contains(c))
+        );
+
+        assert!(parse("requried: lower").is_err());
+        assert!(parse("required: vowels").is_err());
+        assert!(parse("allowed: [abc").is_err());
+        assert!(parse("maxlength: twenty").is_err());
+        assert!(parse("required lower").is_err());
+    }
+}
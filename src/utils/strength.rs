@@ -0,0 +1,646 @@
+// Password strength estimation in the spirit of zxcvbn: the password is split
+// into the cheapest sequence of guessable patterns (common words, keyboard
+// walks, repeats, sequences, dates) and random characters, and the total
+// number of guesses is reported as bits of entropy.
+
+use crate::utils::Utc;
+use crate::utils::generator::eff_wordlist;
+use chrono::Datelike;
+use serde::Serialize;
+use std::collections::HashMap;
+use std::fmt;
+use std::sync::LazyLock;
+
+/// Longer input is scored by its first characters only; the rest counts as random
+const MAX_ANALYZED: usize = 100;
+
+/// No pattern match is ever cheaper than this many bits (10 guesses)
+const MIN_MATCH_BITS: f64 = 3.3;
+
+const LABELS: [&str; 5] = ["very weak", "weak", "fair", "strong", "very strong"];
+
+/// Common passwords and words, most common first; the rank is the guess count
+const COMMON_WORDS: &str = "
+password 123456 qwerty letmein welcome monkey dragon football iloveyou admin
+login master sunshine princess shadow superman trustno1 hello freedom whatever
+baseball michael jennifer jordan hunter ranger buster soccer hockey killer
+george charlie andrew michelle love pepper daniel access joshua maggie starwars
+silver william dallas yankees ashley summer winter spring autumn secret pass
+test user guest root changeme default computer internet samsung apple google
+facebook netflix batman thomas robert matthew jessica amanda nicole taylor
+mustang harley ginger hannah orange banana cheese chocolate cookie coffee
+flower purple yellow diamond golden angel blessed family forever friends
+lovely mother father sister brother baby darling honey sweet happy smile
+summer money power magic secret tiger lion eagle falcon phoenix wolf bear
+dolphin horse chicken pussy cowboy pirate ninja wizard knight dragon1 zombie
+matrix hacker gamer player soccer1 london paris berlin tokyo china canada
+america texas florida boston chicago miami jesus christ heaven church faith
+january february march april june july august september october november
+december monday tuesday wednesday thursday friday saturday sunday morning
+night qwertyuiop asdfgh zxcvbn abc123 password1 iloveyou1 welcome1 admin123
+letmein1 passw0rd p4ssword azerty qwertz office work home house school
+company business manager service support system server network database
+backup cloud mail email account bank money credit security private
+personal public office lucky rainbow thunder storm ocean river mountain
+forest garden spirit energy music guitar piano rock metal jazz movie star
+starlight moon sun earth planet space rocket captain soldier warrior
+legend champion winner victory hero master1 boss king queen prince
+";
+
+/// Guesses for each lowercase common word. Passphrase words all cost the
+/// same, the size of the list they are drawn from.
+static DICTIONARY: LazyLock<HashMap<&'static str, usize>> = LazyLock::new(|| {
+    let mut ranks = HashMap::new();
+    for (i, word) in COMMON_WORDS.split_whitespace().enumerate() {
+        ranks.entry(word).or_insert(i + 1);
+    }
+    let passphrase_words = eff_wordlist();
+    for word in passphrase_words {
+        ranks.entry(word.as_str()).or_insert(passphrase_words.len());
+    }
+    ranks
+});
+
+/// QWERTY rows, unshifted and shifted; each row is half a key further right
+const KEYBOARD: [(&str, &str); 4] = [
+    ("`1234567890-=", "~!@#$%^&*()_+"),
+    ("qwertyuiop[]\\", "QWERTYUIOP{}|"),
+    ("asdfghjkl;'", "ASDFGHJKL:\""),
+    ("zxcvbnm,./", "ZXCVBNM<>?"),
+];
+
+/// Keys a walk can start on and the average number of neighbours per key
+const KEYBOARD_KEYS: f64 = 94.0;
+const KEYBOARD_DEGREE: f64 = 4.6;
+
+/// The kind of weakness a part of the password has
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Pattern {
+    Dictionary,
+    UserInput,
+    Keyboard,
+    Repeat,
+    Sequence,
+    Date,
+}
+
+impl Pattern {
+    fn warning(self) -> &'static str {
+        match self {
+            Pattern::Dictionary => "contains a common password or word",
+            Pattern::UserInput => "contains the service name or username",
+            Pattern::Keyboard => "contains a keyboard pattern like qwerty",
+            Pattern::Repeat => "contains repeats like aaa or abcabc",
+            Pattern::Sequence => "contains a sequence like abc or 6543",
+            Pattern::Date => "contains a date or year",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Strength {
+    /// log2 of the estimated number of guesses
+    pub bits: f64,
+    /// 0 (very weak) to 4 (very strong)
+    pub score: u8,
+    /// The largest guessable part found, if any
+    pub pattern: Option<Pattern>,
+}
+
+impl Strength {
+    /// Rating for a secret whose entropy is known exactly, like a generated passphrase
+    pub fn from_entropy(bits: f64) -> Self {
+        Strength {
+            bits,
+            score: score(bits),
+            pattern: None,
+        }
+    }
+
+    pub fn label(&self) -> &'static str {
+        LABELS[self.score as usize]
+    }
+
+    pub fn is_weak(&self) -> bool {
+        self.score < 2
+    }
+
+    /// Why the password is guessable, e.g. "contains a date or year"
+    pub fn warning(&self) -> Option<&'static str> {
+        self.pattern.map(Pattern::warning)
+    }
+}
+
+impl fmt::Display for Strength {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} ({:.0} bits)", self.label(), self.bits)
+    }
+}
+
+#[derive(Debug)]
+struct Match {
+    start: usize,
+    end: usize,
+    bits: f64,
+    pattern: Pattern,
+}
+
+/// Estimate how hard `password` is to guess. `user_inputs` (service name,
+/// username, ...) count as the most likely words of all.
+pub fn estimate(password: &str, user_inputs: &[&str]) -> Strength {
+    let chars: Vec<char> = password.chars().collect();
+    let analyzed = &chars[..chars.len().min(MAX_ANALYZED)];
+    let random_bits = pool_size(&chars).log2();
+
+    let mut matches = pattern_matches(analyzed, user_inputs);
+    matches.extend(repeat_matches(analyzed, user_inputs));
+    let n = analyzed.len();
+    let (best, choice) = cheapest(n, &matches, random_bits);
+
+    // Report the pattern covering the most characters
+    let mut pattern: Option<&Match> = None;
+    let mut end = n;
+    while end > 0 {
+        match choice[end] {
+            Some(i) => {
+                let m = &matches[i];
+                if pattern.is_none_or(|p| m.end - m.start > p.end - p.start) {
+                    pattern = Some(m);
+                }
+                end = m.start;
+            }
+            None => end -= 1,
+        }
+    }
+
+    let bits = best[n] + (chars.len() - n) as f64 * random_bits;
+    Strength {
+        bits,
+        score: score(bits),
+        pattern: pattern.map(|m| m.pattern),
+    }
+}
+
+/// Cheapest way to produce each prefix of `n` characters: a random character
+/// or a pattern, with the match that ends each prefix
+fn cheapest(n: usize, matches: &[Match], random_bits: f64) -> (Vec<f64>, Vec<Option<usize>>) {
+    let mut best = vec![0.0; n + 1];
+    let mut choice: Vec<Option<usize>> = vec![None; n + 1];
+    for end in 1..=n {
+        best[end] = best[end - 1] + random_bits;
+        for (i, m) in matches.iter().enumerate().filter(|(_, m)| m.end == end) {
+            let bits = best[m.start] + m.bits.max(MIN_MATCH_BITS);
+            if bits < best[end] {
+                best[end] = bits;
+                choice[end] = Some(i);
+            }
+        }
+    }
+    (best, choice)
+}
+
+fn score(bits: f64) -> u8 {
+    match bits {
+        b if b < 25.0 => 0,
+        b if b < 40.0 => 1,
+        b if b < 60.0 => 2,
+        b if b < 80.0 => 3,
+        _ => 4,
+    }
+}
+
+/// Size of the character pool a brute-force attack would have to try
+fn pool_size(chars: &[char]) -> f64 {
+    let has = |f: fn(&char) -> bool| chars.iter().any(f);
+    let mut pool = 0.0;
+    if has(char::is_ascii_lowercase) {
+        pool += 26.0;
+    }
+    if has(char::is_ascii_uppercase) {
+        pool += 26.0;
+    }
+    if has(char::is_ascii_digit) {
+        pool += 10.0;
+    }
+    if has(|c| c.is_ascii_punctuation() || *c == ' ') {
+        pool += 33.0;
+    }
+    if has(|c| !c.is_ascii()) {
+        pool += 100.0;
+    }
+    f64::max(pool, 10.0)
+}
+
+/// Every match except repeats, which are scored from these
+fn pattern_matches(chars: &[char], user_inputs: &[&str]) -> Vec<Match> {
+    let mut matches = dictionary_matches(chars, user_inputs);
+    matches.extend(keyboard_matches(chars));
+    matches.extend(sequence_matches(chars));
+    matches.extend(date_matches(chars));
+    matches
+}
+
+/// Undo common letter substitutions such as p4ssw0rd
+fn unleet(c: char) -> char {
+    match c {
+        '4' | '@' => 'a',
+        '3' => 'e',
+        '1' | '!' => 'i',
+        '|' => 'l',
+        '0' => 'o',
+        '$' | '5' => 's',
+        '7' => 't',
+        _ => c,
+    }
+}
+
+fn dictionary_matches(chars: &[char], user_inputs: &[&str]) -> Vec<Match> {
+    let mut user_words: HashMap<String, usize> = HashMap::new();
+    for input in user_inputs {
+        let input = input.to_lowercase();
+        let parts = input.split(|c: char| !c.is_alphanumeric());
+        for word in std::iter::once(input.as_str()).chain(parts) {
+            if word.chars().count() >= 3 {
+                let rank = user_words.len() + 1;
+                user_words.entry(word.to_string()).or_insert(rank);
+            }
+        }
+    }
+
+    let lower: Vec<char> = chars.iter().flat_map(|c| c.to_lowercase()).collect();
+    if lower.len() != chars.len() {
+        return Vec::new();
+    }
+    let unleeted: Vec<char> = lower.iter().map(|&c| unleet(c)).collect();
+
+    let mut matches = Vec::new();
+    for start in 0..chars.len() {
+        for end in start + 3..=chars.len() {
+            let original = &chars[start..end];
+            let candidates = [
+                (lower[start..end].iter().collect::<String>(), 1.0),
+                (unleeted[start..end].iter().collect(), leet_factor(original)),
+                (lower[start..end].iter().rev().collect(), 2.0),
+            ];
+            for (word, factor) in candidates {
+                let (rank, pattern) = match user_words.get(&word) {
+                    Some(&rank) => (rank, Pattern::UserInput),
+                    None => match DICTIONARY.get(word.as_str()) {
+                        Some(&rank) => (rank, Pattern::Dictionary),
+                        None => continue,
+                    },
+                };
+                let guesses = rank as f64 * factor * case_factor(original);
+                matches.push(Match {
+                    start,
+                    end,
+                    bits: guesses.log2(),
+                    pattern,
+                });
+            }
+        }
+    }
+    matches
+}
+
+/// Extra guesses for capitalisation: Password and PASSWORD are tried early
+fn case_factor(word: &[char]) -> f64 {
+    let upper = word.iter().filter(|c| c.is_uppercase()).count();
+    let lower = word.iter().filter(|c| c.is_lowercase()).count();
+    if upper == 0 {
+        1.0
+    } else if lower == 0 || (upper == 1 && word[0].is_uppercase()) {
+        2.0
+    } else {
+        2f64.powi(upper.min(lower) as i32 + 1)
+    }
+}
+
+fn leet_factor(word: &[char]) -> f64 {
+    let substituted = word
+        .iter()
+        .filter(|&&c| unleet(c.to_ascii_lowercase()) != c.to_ascii_lowercase())
+        .count();
+    2f64.powi(substituted as i32)
+}
+
+/// Row, half-key column and shift state of a key
+fn key_position(c: char) -> Option<(i32, i32, bool)> {
+    KEYBOARD
+        .iter()
+        .enumerate()
+        .find_map(|(row, (plain, shifted))| {
+            let (col, shift) = match plain.chars().position(|k| k == c) {
+                Some(col) => (col, false),
+                None => (shifted.chars().position(|k| k == c)?, true),
+            };
+            Some((row as i32, 2 * col as i32 + row as i32, shift))
+        })
+}
+
+fn keyboard_matches(chars: &[char]) -> Vec<Match> {
+    let mut matches = Vec::new();
+    let mut start = 0;
+    while start + 2 < chars.len() {
+        let mut end = start + 1;
+        let mut turns = 0;
+        let mut direction = None;
+        let mut shifted = usize::from(key_position(chars[start]).is_some_and(|k| k.2));
+
+        while end < chars.len() {
+            let (Some(prev), Some(next)) = (key_position(chars[end - 1]), key_position(chars[end]))
+            else {
+                break;
+            };
+            let step = (next.0 - prev.0, next.1 - prev.1);
+            let adjacent = match step {
+                (0, dx) => dx.abs() == 2,
+                (dy, dx) => dy.abs() == 1 && dx.abs() == 1,
+            };
+            if !adjacent {
+                break;
+            }
+            if direction != Some(step) {
+                turns += 1;
+                direction = Some(step);
+            }
+            shifted += usize::from(next.2);
+            end += 1;
+        }
+
+        if end - start >= 3 {
+            let guesses = keyboard_guesses(end - start, turns, shifted);
+            matches.push(Match {
+                start,
+                end,
+                bits: guesses.log2(),
+                pattern: Pattern::Keyboard,
+            });
+            start = end - 1;
+        } else {
+            start += 1;
+        }
+    }
+    matches
+}
+
+/// zxcvbn's spatial estimate: every start key, length and placement of turns
+fn keyboard_guesses(length: usize, turns: usize, shifted: usize) -> f64 {
+    let mut guesses = 0.0;
+    for i in 2..=length {
+        for j in 1..=turns.min(i - 1) {
+            guesses += binomial(i - 1, j - 1) * KEYBOARD_KEYS * KEYBOARD_DEGREE.powi(j as i32);
+        }
+    }
+
+    let unshifted = length - shifted;
+    if shifted == 0 {
+        guesses
+    } else if unshifted == 0 {
+        guesses * 2.0
+    } else {
+        let variations: f64 = (1..=shifted.min(unshifted))
+            .map(|i| binomial(length, i))
+            .sum();
+        guesses * variations
+    }
+}
+
+fn binomial(n: usize, k: usize) -> f64 {
+    (0..k).fold(1.0, |acc, i| acc * (n - i) as f64 / (i + 1) as f64)
+}
+
+fn repeat_matches(chars: &[char], user_inputs: &[&str]) -> Vec<Match> {
+    // Bases recur a lot ("abab..." repeats "ab" from every other start)
+    let mut base_bits: HashMap<&[char], f64> = HashMap::new();
+    let mut matches = Vec::new();
+    for start in 0..chars.len() {
+        for unit in 1..=(chars.len() - start) / 2 {
+            let base = &chars[start..start + unit];
+            let count = chars[start..]
+                .chunks(unit)
+                .take_while(|chunk| *chunk == base)
+                .count();
+            if count < 2 || (unit == 1 && count < 3) {
+                continue;
+            }
+            // A repeat inside the base is not looked for again, which keeps
+            // this linear in the number of bases instead of exponential
+            let bits = *base_bits.entry(base).or_insert_with(|| {
+                let base_matches = pattern_matches(base, user_inputs);
+                cheapest(unit, &base_matches, pool_size(base).log2()).0[unit]
+            });
+            matches.push(Match {
+                start,
+                end: start + unit * count,
+                bits: bits + (count as f64).log2(),
+                pattern: Pattern::Repeat,
+            });
+        }
+    }
+    matches
+}
+
+fn sequence_matches(chars: &[char]) -> Vec<Match> {
+    let same_class = |a: char, b: char| {
+        (a.is_ascii_lowercase() && b.is_ascii_lowercase())
+            || (a.is_ascii_uppercase() && b.is_ascii_uppercase())
+            || (a.is_ascii_digit() && b.is_ascii_digit())
+    };
+
+    let mut matches = Vec::new();
+    let mut start = 0;
+    while start + 2 < chars.len() {
+        let delta = chars[start + 1] as i32 - chars[start] as i32;
+        let mut end = start + 1;
+        if delta.abs() == 1 {
+            while end < chars.len()
+                && same_class(chars[start], chars[end])
+                && chars[end] as i32 - chars[end - 1] as i32 == delta
+            {
+                end += 1;
+            }
+        }
+
+        if end - start >= 3 {
+            let first = chars[start];
+            let base = if "aAzZ019".contains(first) {
+                4.0
+            } else if first.is_ascii_digit() {
+                10.0
+            } else {
+                26.0
+            };
+            let direction = if delta < 0 { 2.0 } else { 1.0 };
+            let guesses = base * (end - start) as f64 * direction;
+            matches.push(Match {
+                start,
+                end,
+                bits: guesses.log2(),
+                pattern: Pattern::Sequence,
+            });
+            start = end - 1;
+        } else {
+            start += 1;
+        }
+    }
+    matches
+}
+
+fn date_matches(chars: &[char]) -> Vec<Match> {
+    let mut matches = Vec::new();
+    for start in 0..chars.len() {
+        for end in start + 4..=chars.len().min(start + 10) {
+            let text: String = chars[start..end].iter().collect();
+            if let Some(guesses) = date_guesses(&text) {
+                matches.push(Match {
+                    start,
+                    end,
+                    bits: guesses.log2(),
+                    pattern: Pattern::Date,
+                });
+            }
+        }
+    }
+    matches
+}
+
+/// Guesses for a year (1987) or a full date (12/03/1987, 19871203, 3.12.87)
+fn date_guesses(text: &str) -> Option<f64> {
+    // Years close to now are the likeliest guesses
+    let current_year = Utc::now().year();
+    let year_space = |year: i32| f64::from((year - current_year).abs().max(20));
+
+    if !text.chars().next()?.is_ascii_digit() || !text.chars().last()?.is_ascii_digit() {
+        return None;
+    }
+
+    if text.chars().all(|c| c.is_ascii_digit()) {
+        let parts: &[&[usize]] = match text.len() {
+            4 => {
+                let year: i32 = text.parse().ok()?;
+                return (1900..=2099).contains(&year).then(|| year_space(year));
+            }
+            6 => &[&[2, 2, 2]],
+            8 => &[&[2, 2, 4], &[4, 2, 2]],
+            _ => return None,
+        };
+        return parts.iter().find_map(|lengths| {
+            let mut rest = text;
+            let fields: Vec<&str> = lengths
+                .iter()
+                .map(|&len| {
+                    let (field, tail) = rest.split_at(len);
+                    rest = tail;
+                    field
+                })
+                .collect();
+            date_year(&fields).map(|year| 365.0 * year_space(year))
+        });
+    }
+
+    let separator = text.chars().find(|c| !c.is_ascii_digit())?;
+    if !"-/._ ".contains(separator) {
+        return None;
+    }
+    let fields: Vec<&str> = text.split(separator).collect();
+    if fields.len() != 3 || fields.iter().any(|f| f.is_empty() || f.len() > 4) {
+        return None;
+    }
+    date_year(&fields).map(|year| 365.0 * year_space(year) * 4.0)
+}
+
+/// The year of a valid day-month-year, month-day-year or year-month-day
+fn date_year(fields: &[&str]) -> Option<i32> {
+    let numbers: Vec<i32> = fields
+        .iter()
+        .map(|f| f.parse().ok())
+        .collect::<Option<_>>()?;
+    let (a, b, c) = (numbers[0], numbers[1], numbers[2]);
+    let valid = |day: i32, month: i32| (1..=31).contains(&day) && (1..=12).contains(&month);
+    let full_year = |year: i32, len: usize| match len {
+        2 if year >= 50 => Some(1900 + year),
+        2 => Some(2000 + year),
+        4 if (1900..=2099).contains(&year) => Some(year),
+        _ => None,
+    };
+
+    if fields[0].len() == 4 {
+        return full_year(a, 4).filter(|_| valid(c, b));
+    }
+    if fields[0].len() > 2 || fields[1].len() > 2 {
+        return None;
+    }
+    let year = full_year(c, fields[2].len())?;
+    (valid(a, b) || valid(b, a)).then_some(year)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Instant;
+
+    #[test]
+    fn test_common_patterns_are_weak() {
+        for password in [
+            "password",
+            "P@ssw0rd",
+            "qwertyuiop",
+            "aaaaaaaaaa",
+            "abcdefgh",
+            "12345678",
+            "19870312",
+            "12/03/1987",
+            "dragondragon",
+        ] {
+            let strength = estimate(password, &[]);
+            assert!(strength.is_weak(), "{} scored {}", password, strength);
+            assert!(strength.pattern.is_some(), "{} had no pattern", password);
+        }
+
+        let strength = estimate("GitLab2024", &["GitLab"]);
+        assert!(strength.is_weak());
+        assert_eq!(strength.pattern, Some(Pattern::UserInput));
+    }
+
+    #[test]
+    fn test_long_repeats() {
+        for unit in ["a", "ab", "abc1", "password", "Tr0ub4dor&"] {
+            let password = unit.repeat(MAX_ANALYZED / unit.len());
+            let strength = estimate(&password, &[]);
+            assert_eq!(strength.pattern, Some(Pattern::Repeat), "{}", unit);
+            assert!(strength.bits < 80.0, "{} scored {}", unit, strength);
+        }
+    }
+
+    #[test]
+    fn test_random_passwords_are_strong() {
+        assert_eq!(estimate("k9#Vq2!mZt@4Lw8&Xr", &[]).score, 4);
+        assert!(estimate("h7Fq2mXz9pLw", &[]).score >= 3);
+        assert!(estimate("Summer1987!", &[]).bits < estimate("sUm7e!r19qX", &[]).bits);
+        assert_eq!(estimate("", &[]).score, 0);
+    }
+
+    #[test]
+    #[ignore = "benchmark: cargo test --release bench_ -- --ignored --nocapture"]
+    fn bench_estimate() {
+        // Build the dictionary first so only the estimates are timed
+        estimate("warm up", &[]);
+        // Nesting the estimate inside repeats once made this take seconds
+        for unit in ["a", "Tr0ub4dor&"] {
+            let password = unit.repeat(MAX_ANALYZED / unit.len());
+            let start = Instant::now();
+            estimate(&password, &[]);
+            println!(
+                "estimate({:?} x {}): {:?}",
+                unit,
+                MAX_ANALYZED / unit.len(),
+                start.elapsed()
+            );
+        }
+        let start = Instant::now();
+        estimate("k9#Vq2!mZt@4Lw8&Xr", &[]);
+        println!("estimate(random 18 chars): {:?}", start.elapsed());
+    }
+}